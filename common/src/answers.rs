@@ -0,0 +1,341 @@
+//! The answers of each day, kept in `answers.toml` in the data directory
+//! rather than in the tests, as they depend on whose input it is:
+//!
+//! ```toml
+//! [day01.default]
+//! part1 = "964"
+//! part2 = "5872"
+//! ```
+//!
+//! `default` is the answer for `dayNN.txt`; other inputs go under their own
+//! name.
+
+use crate::error::{Error, Result};
+use crate::input::{data_dir, input_available, input_name, input_sets};
+use crate::solution::Solution;
+use crate::variant::disagreements;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, Table, value};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Name of the answers for `dayNN.txt`.
+pub const DEFAULT_INPUT: &str = "default";
+
+/// How an answer compares with the recorded one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// There is no recorded answer.
+    Unknown,
+}
+
+impl Check {
+    pub fn of(expected: Option<&str>, actual: &str) -> Self {
+        match expected {
+            None => Check::Unknown,
+            Some(expected) if expected == actual => Check::Pass,
+            Some(expected) => Check::Fail {
+                expected: expected.to_owned(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Check::Pass => "pass",
+            Check::Fail { .. } => "fail",
+            Check::Unknown => "unknown",
+        })
+    }
+}
+
+/// The contents of an answers file, which keeps its comments and layout
+/// when answers are added.
+#[derive(Clone, Debug)]
+pub struct Answers {
+    path: PathBuf,
+    doc: DocumentMut,
+}
+
+impl Answers {
+    /// Loads `answers.toml` from the data directory (see `input::data_dir`).
+    pub fn load() -> Result<Self> {
+        Self::load_from(data_dir()?.join(ANSWERS_FILE))
+    }
+
+    /// Loads the answers in `path`, none if there is no such file.
+    pub fn load_from(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(source) => return Err(Error::Io { path, source }),
+        };
+        let doc = text.parse::<DocumentMut>().map_err(|e| Error::Answers {
+            path: path.clone(),
+            message: e.message().to_owned(),
+        })?;
+        validate(&doc).map_err(|message| Error::Answers {
+            path: path.clone(),
+            message,
+        })?;
+        Ok(Self { path, doc })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The answer of `part` of `day` on the input called `input`.
+    pub fn get(&self, day: u32, part: u32, input: &str) -> Option<String> {
+        let answer = self
+            .doc
+            .get(&day_key(day))?
+            .get(input)?
+            .get(part_key(part))?;
+        answer
+            .as_str()
+            .map(str::to_owned)
+            .or_else(|| answer.as_integer().map(|n| n.to_string()))
+    }
+
+    pub fn check(&self, day: u32, part: u32, input: &str, actual: &str) -> Check {
+        Check::of(self.get(day, part, input).as_deref(), actual)
+    }
+
+    pub fn set(&mut self, day: u32, part: u32, input: &str, answer: &str) {
+        let day = implicit_table(self.doc.as_table_mut(), &day_key(day));
+        let input = implicit_table(day, input);
+        input.set_implicit(false);
+        input[&part_key(part)] = value(answer);
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let io_error = |source| Error::Io {
+            path: self.path.clone(),
+            source,
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        fs::write(&self.path, self.doc.to_string()).map_err(io_error)
+    }
+}
+
+/// For the tests of a day: checks the answer of `part` on the default
+/// input, computed by `solve`, against the recorded one. A missing answer is
+/// reported but does not fail the test, and without the input (see
+/// `input::input_available`) the test is skipped.
+pub fn check_answer<T: fmt::Display>(day: u32, part: u32, solve: impl FnOnce() -> T) {
+    if !input_available(&input_name(day)) {
+        return;
+    }
+    let answers = Answers::load().unwrap_or_else(|e| panic!("{}", e));
+    let actual = solve().to_string();
+    match answers.check(day, part, DEFAULT_INPUT, &actual) {
+        Check::Pass => {}
+        Check::Fail { expected } => panic!(
+            "day {} part {}: got {}, but {} has {}",
+            day,
+            part,
+            actual,
+            answers.path().display(),
+            expected
+        ),
+        Check::Unknown => eprintln!(
+            "day {} part {}: got {}, no answer in {} to check it against",
+            day,
+            part,
+            actual,
+            answers.path().display()
+        ),
+    }
+}
+
+/// For the tests of a day: solves every input set of the day (see
+/// `input::input_sets`) and checks the answers recorded for it, and that the
+/// variants of the day agree with them. Parts without an answer are
+/// reported, and a day without inputs is skipped.
+pub fn check_input_sets(solution: &dyn Solution) {
+    let day = solution.day();
+    let sets = input_sets(day);
+    if sets.is_empty() && !input_available(&input_name(day)) {
+        return;
+    }
+    let answers = Answers::load().unwrap_or_else(|e| panic!("{}", e));
+    let mut failures = Vec::new();
+    for set in sets {
+        let input = match set.read() {
+            Ok(input) => input,
+            Err(e) => {
+                failures.push(format!("{}: {}", set, e));
+                continue;
+            }
+        };
+        let parsed = match solution.parse(&input) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                failures.push(format!("{}: {}", set, e));
+                continue;
+            }
+        };
+        let parts = [(1, Some(parsed.part1())), (2, parsed.part2())];
+        for (part, outcome) in parts {
+            let actual = match outcome {
+                None => continue,
+                Some(Ok(actual)) => actual,
+                Some(Err(e)) => {
+                    failures.push(format!("{} part {}: {}", set, part, e));
+                    continue;
+                }
+            };
+            match answers.check(day, part, &set.name, &actual) {
+                Check::Pass => {}
+                Check::Fail { expected } => failures.push(format!(
+                    "{} part {}: got {}, but {} has {}",
+                    set,
+                    part,
+                    actual,
+                    answers.path().display(),
+                    expected
+                )),
+                Check::Unknown => eprintln!(
+                    "day {} {} part {}: got {}, no answer to check it against",
+                    day, set, part, actual
+                ),
+            }
+        }
+        failures.extend(
+            disagreements(solution, parsed.as_ref())
+                .into_iter()
+                .map(|e| format!("{} {}", set, e)),
+        );
+    }
+    assert!(failures.is_empty(), "day {}:\n{}", day, failures.join("\n"));
+}
+
+/// For the tests of a day: checks that every variant of the day agrees with
+/// its part on `input`, typically the example.
+pub fn check_variants(solution: &dyn Solution, input: &str) {
+    let parsed = solution
+        .parse(input)
+        .unwrap_or_else(|e| panic!("Failed to parse input: {}", e));
+    let failures = disagreements(solution, parsed.as_ref());
+    assert!(
+        failures.is_empty(),
+        "day {}:\n{}",
+        solution.day(),
+        failures.join("\n")
+    );
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{:02}", day)
+}
+
+fn part_key(part: u32) -> String {
+    format!("part{}", part)
+}
+
+fn implicit_table<'a>(parent: &'a mut Table, key: &str) -> &'a mut Table {
+    let item = parent.entry(key).or_insert_with(|| {
+        let mut table = Table::new();
+        table.set_implicit(true);
+        Item::Table(table)
+    });
+    item.as_table_mut()
+        .expect("answers files are validated on load")
+}
+
+// every day is a table of inputs, each a table of answers
+fn validate(doc: &DocumentMut) -> std::result::Result<(), String> {
+    for (day, inputs) in doc.iter() {
+        let inputs = inputs
+            .as_table_like()
+            .ok_or_else(|| format!("{} is not a table of inputs", day))?;
+        for (input, answers) in inputs.iter() {
+            let answers = answers
+                .as_table_like()
+                .ok_or_else(|| format!("{}.{} is not a table of answers", day, input))?;
+            for (part, answer) in answers.iter() {
+                if answer.as_str().is_none() && answer.as_integer().is_none() {
+                    return Err(format!("{}.{}.{} is not an answer", day, input, part));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("common-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_get_and_check() {
+        let path = temp_path("answers.toml");
+        fs::write(
+            &path,
+            "# ours\n[day01.default]\npart1 = \"964\"\npart2 = 5872\n\n[day01.ada]\npart1 = \"12\"\n",
+        )
+        .unwrap();
+        let answers = Answers::load_from(&path).unwrap();
+        assert_eq!(answers.get(1, 1, DEFAULT_INPUT).as_deref(), Some("964"));
+        assert_eq!(answers.get(1, 2, DEFAULT_INPUT).as_deref(), Some("5872"));
+        assert_eq!(answers.check(1, 1, "ada", "12"), Check::Pass);
+        assert_eq!(
+            answers.check(1, 1, DEFAULT_INPUT, "963"),
+            Check::Fail {
+                expected: "964".to_owned()
+            }
+        );
+        assert_eq!(answers.check(1, 2, "ada", "1"), Check::Unknown);
+        assert_eq!(answers.check(2, 1, DEFAULT_INPUT, "1"), Check::Unknown);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_set_keeps_the_rest_of_the_file() {
+        let path = temp_path("set/answers.toml");
+        let _ = fs::remove_file(&path);
+        let mut answers = Answers::load_from(&path).unwrap();
+        answers.set(5, 1, DEFAULT_INPUT, "563");
+        answers.save().unwrap();
+        let mut answers = Answers::load_from(&path).unwrap();
+        answers.set(5, 2, DEFAULT_INPUT, "338693411431456");
+        answers.set(12, 1, "ada", "510");
+        answers.save().unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "[day05.default]\npart1 = \"563\"\npart2 = \"338693411431456\"\n\n[day12.ada]\npart1 = \"510\"\n"
+        );
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_invalid_files() {
+        let path = temp_path("invalid.toml");
+        for text in ["[day01", "day01 = 1", "[day01.default]\npart1 = [1]"] {
+            fs::write(&path, text).unwrap();
+            assert!(matches!(
+                Answers::load_from(&path),
+                Err(Error::Answers { .. })
+            ));
+        }
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,47 @@
+//! The benches every day has on its input sets, so that each `benchmarks.rs`
+//! only names its day:
+//!
+//! ```ignore
+//! common::bench_input_sets!(Day05);
+//! common::bench_input_sets!(Day12, part1); // no part 2
+//! ```
+
+/// Adds the divan benches `bench_parse_input_sets`, timing the parsing of
+/// each input set of the day, and `bench_part1_input_sets` and
+/// `bench_part2_input_sets`, timing its parts on the input set already
+/// parsed. The `default` set is left out, as `bench_parse` and `bench_partN`
+/// already time it. The crate using it needs `divan`.
+#[macro_export]
+macro_rules! bench_input_sets {
+    (@part $day:expr, part1) => {
+        $crate::bench_input_sets!(@bench $day, bench_part1_input_sets, part1);
+    };
+    (@part $day:expr, part2) => {
+        $crate::bench_input_sets!(@bench $day, bench_part2_input_sets, part2);
+    };
+    (@sets $day:expr) => {
+        $crate::input_sets($crate::Day::day(&$day))
+            .into_iter()
+            .filter(|set| set.name != $crate::answers::DEFAULT_INPUT)
+            .collect::<Vec<_>>()
+    };
+    (@bench $day:expr, $name:ident, $part:ident) => {
+        #[divan::bench(args = $crate::bench_input_sets!(@sets $day))]
+        fn $name(bencher: divan::Bencher, set: &$crate::InputSet) {
+            let input = set.read().expect("Failed to load input");
+            let model = $crate::Day::parse(&$day, &input).expect("Failed to parse input");
+            bencher.bench(|| $crate::Day::$part(&$day, divan::black_box(&model)));
+        }
+    };
+    ($day:expr) => {
+        $crate::bench_input_sets!($day, part1, part2);
+    };
+    ($day:expr, $($part:ident),+) => {
+        #[divan::bench(args = $crate::bench_input_sets!(@sets $day))]
+        fn bench_parse_input_sets(bencher: divan::Bencher, set: &$crate::InputSet) {
+            let input = set.read().expect("Failed to load input");
+            bencher.bench(|| $crate::Day::parse(&$day, divan::black_box(&input)));
+        }
+        $($crate::bench_input_sets!(@part $day, $part);)+
+    };
+}
@@ -0,0 +1,140 @@
+/// Disjoint-set union (union-find) over the elements `0..n`.
+///
+/// Uses union by size and path compression, so any sequence of operations
+/// runs in almost constant amortized time per operation.
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    // only meaningful for roots
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// Starts with every element in its own component.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Representative of the component of `i`.
+    pub fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // second pass to make the whole path point to the root
+        let mut i = i;
+        while self.parent[i] != root {
+            let next = self.parent[i];
+            self.parent[i] = root;
+            i = next;
+        }
+        root
+    }
+
+    /// Merges the components of `i` and `j`; false if they already were one.
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (mut pi, mut pj) = (self.find(i), self.find(j));
+        if pi == pj {
+            return false;
+        }
+        if self.size[pi] < self.size[pj] {
+            std::mem::swap(&mut pi, &mut pj);
+        }
+        self.parent[pj] = pi;
+        self.size[pi] += self.size[pj];
+        self.components -= 1;
+        true
+    }
+
+    pub fn same(&mut self, i: usize, j: usize) -> bool {
+        self.find(i) == self.find(j)
+    }
+
+    /// Size of the component of `i`.
+    pub fn size_of(&mut self, i: usize) -> usize {
+        let root = self.find(i);
+        self.size[root]
+    }
+
+    /// Number of components.
+    pub fn count(&self) -> usize {
+        self.components
+    }
+
+    /// Members of each component, in increasing order of their smallest one.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut slot = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for i in 0..self.len() {
+            let root = self.find(i);
+            if slot[root] == usize::MAX {
+                slot[root] = components.len();
+                components.push(Vec::with_capacity(self.size[root]));
+            }
+            components[slot[root]].push(i);
+        }
+        components
+    }
+
+    /// Sizes of the components, largest first.
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes = (0..self.len())
+            .filter(|&i| self.parent[i] == i)
+            .map(|i| self.size[i])
+            .collect::<Vec<_>>();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_and_find() {
+        let mut dsu = DisjointSet::new(6);
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(2, 3));
+        assert!(dsu.union(1, 3));
+        assert!(!dsu.union(0, 2));
+        assert!(dsu.same(0, 3));
+        assert!(!dsu.same(0, 4));
+        assert_eq!(dsu.size_of(2), 4);
+        assert_eq!(dsu.count(), 3);
+    }
+
+    #[test]
+    fn test_components_and_sizes() {
+        let mut dsu = DisjointSet::new(6);
+        dsu.union(4, 1);
+        dsu.union(5, 3);
+        dsu.union(3, 1);
+        assert_eq!(dsu.components(), vec![vec![0], vec![1, 3, 4, 5], vec![2]]);
+        assert_eq!(dsu.sizes(), vec![4, 1, 1]);
+    }
+
+    #[test]
+    fn test_long_chain_is_compressed() {
+        let n = 100_000;
+        let mut dsu = DisjointSet::new(n);
+        for i in 1..n {
+            dsu.union(i - 1, i);
+        }
+        assert_eq!(dsu.count(), 1);
+        assert_eq!(dsu.sizes(), vec![n]);
+    }
+}
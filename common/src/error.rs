@@ -0,0 +1,145 @@
+use std::fmt;
+use std::io;
+use std::num::ParseIntError;
+use std::path::PathBuf;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input file could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The workspace root, from which input paths are resolved, is unknown.
+    MissingRootDir,
+    /// The input was read but its contents are not valid.
+    Parse(ParseError),
+    /// The answers file is not valid TOML, or has something else than
+    /// answers in it.
+    Answers { path: PathBuf, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "cannot read {}: {}", path.display(), source),
+            Error::MissingRootDir => write!(f, "unable to get to root dir"),
+            Error::Parse(e) => e.fmt(f),
+            Error::Answers { path, message } => write!(f, "{}: {}", path.display(), message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::MissingRootDir | Error::Answers { .. } => None,
+            Error::Parse(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+/// A parse failure located in the input.
+///
+/// Parsers of a single line only know the column and the offending text;
+/// whoever splits the input into lines adds the line number (`in_line`) and
+/// whoever read it from disk adds the file (`in_file`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub file: Option<String>,
+    /// 1-based line of the input.
+    pub line: usize,
+    /// 1-based column where the offending text starts.
+    pub column: usize,
+    pub text: String,
+}
+
+impl ParseError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            file: None,
+            line: 1,
+            column: 1,
+            text: String::new(),
+        }
+    }
+
+    /// Locates the error at `column` of its line, where `text` starts.
+    pub fn at(mut self, column: usize, text: &str) -> Self {
+        self.column = column;
+        self.text = text.to_owned();
+        self
+    }
+
+    /// Shifts the column by `offset` chars, for parsers of a part of a line.
+    pub fn shifted(mut self, offset: usize) -> Self {
+        self.column += offset;
+        self
+    }
+
+    /// Places the error in `line` (1-based), whose contents are `text`.
+    ///
+    /// The offending text found by the line parser, if any, is kept.
+    pub fn in_line(mut self, line: usize, text: &str) -> Self {
+        self.line = line;
+        if self.text.is_empty() {
+            self.text = text.to_owned();
+        }
+        self
+    }
+
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_owned());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}: ", file, self.line, self.column)?,
+            None => write!(f, "line {}, column {}: ", self.line, self.column)?,
+        }
+        write!(f, "{} in {:?}", self.message, self.text)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseIntError> for ParseError {
+    fn from(e: ParseIntError) -> Self {
+        ParseError::new(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location_is_kept_from_line_parser() {
+        let e = ParseError::new("invalid digit")
+            .at(3, "x")
+            .shifted(4)
+            .in_line(2, "12-x5")
+            .in_file("data/day05.txt");
+        assert_eq!(e.to_string(), r#"data/day05.txt:2:7: invalid digit in "x""#);
+    }
+
+    #[test]
+    fn test_whole_line_is_used_when_no_text() {
+        let e = ParseError::from("a".parse::<u32>().unwrap_err()).in_line(5, "abc");
+        assert_eq!(
+            e.to_string(),
+            r#"line 5, column 1: invalid digit found in string in "abc""#
+        );
+    }
+}
@@ -0,0 +1,492 @@
+use crate::ParseError;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::ops::{Add, Bound::Included, Mul, RangeInclusive, Sub};
+use std::str::FromStr;
+
+/// Signed integers usable as coordinates.
+pub trait Coord: Copy + Ord + Debug + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// Type wide enough for products of differences of coordinates.
+    type Wide: Copy
+        + Ord
+        + Debug
+        + Default
+        + Add<Output = Self::Wide>
+        + Sub<Output = Self::Wide>
+        + Mul<Output = Self::Wide>;
+
+    fn widen(self) -> Self::Wide;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty => $wide:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                type Wide = $wide;
+
+                fn widen(self) -> $wide {
+                    self as $wide
+                }
+            }
+        )*
+    };
+}
+
+impl_coord!(i16 => i32, i32 => i64, i64 => i128, isize => i128);
+
+/// Ordered first by `x` and then by `y`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// Parses `x,y`.
+impl<T> FromStr for Point<T>
+where
+    T: FromStr,
+    T::Err: Into<ParseError>,
+{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (x_str, y_str) = s
+            .split_once(',')
+            .ok_or_else(|| ParseError::new("Missing ','").at(1, s))?;
+        let x = x_str.parse().map_err(|e: T::Err| e.into().at(1, x_str))?;
+        let y = y_str
+            .parse()
+            .map_err(|e: T::Err| e.into().at(x_str.len() + 2, y_str))?;
+        Ok(Point { x, y })
+    }
+}
+
+/// Cross product of `o -> a` and `o -> b`.
+///
+/// Positive when `o, a, b` turn counter-clockwise with the y axis pointing
+/// up (clockwise when it points down, as in puzzle maps), zero when they are
+/// collinear.
+pub fn cross<T: Coord>(o: &Point<T>, a: &Point<T>, b: &Point<T>) -> T::Wide {
+    let ax = a.x.widen() - o.x.widen();
+    let ay = a.y.widen() - o.y.widen();
+    let bx = b.x.widen() - o.x.widen();
+    let by = b.y.widen() - o.y.widen();
+    ax * by - ay * bx
+}
+
+/// Turn direction, with the y axis pointing up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Collinear,
+}
+
+impl Orientation {
+    fn of<W: Ord + Default>(value: W) -> Self {
+        match value.cmp(&W::default()) {
+            std::cmp::Ordering::Less => Orientation::Clockwise,
+            std::cmp::Ordering::Equal => Orientation::Collinear,
+            std::cmp::Ordering::Greater => Orientation::CounterClockwise,
+        }
+    }
+}
+
+pub fn orientation<T: Coord>(o: &Point<T>, a: &Point<T>, b: &Point<T>) -> Orientation {
+    Orientation::of(cross(o, a, b))
+}
+
+/// Twice the signed area of `polygon` (shoelace formula), positive when its
+/// vertices go counter-clockwise. Doubled so that it stays an integer.
+pub fn signed_area2<T: Coord>(polygon: &[Point<T>]) -> T::Wide {
+    let n = polygon.len();
+    (0..n).fold(T::Wide::default(), |acc, i| {
+        let (p, q) = (polygon[i], polygon[(i + 1) % n]);
+        acc + p.x.widen() * q.y.widen() - q.x.widen() * p.y.widen()
+    })
+}
+
+/// Direction in which the vertices of `polygon` go around it.
+pub fn polygon_orientation<T: Coord>(polygon: &[Point<T>]) -> Orientation {
+    Orientation::of(signed_area2(polygon))
+}
+
+fn half_border<'a, T: Coord + 'a>(points: impl Iterator<Item = &'a Point<T>>) -> Vec<Point<T>> {
+    let mut border: Vec<Point<T>> = Vec::new();
+    for &p in points {
+        // only allow advance if clockwise
+        while border.len() >= 2
+            && cross(&border[border.len() - 2], &border[border.len() - 1], &p) >= T::Wide::default()
+        {
+            border.pop();
+        }
+        border.push(p);
+    }
+    border
+}
+
+/// Vertices of the convex hull, without collinear ones (monotone chain).
+pub fn convex_hull<T: Coord>(points: &[Point<T>]) -> Vec<Point<T>> {
+    let mut pts = points.to_vec();
+    if pts.len() <= 1 {
+        return pts;
+    }
+    pts.sort_unstable(); // lexicographically: first x, then y
+    let mut lower = half_border(pts.iter());
+    let mut upper = half_border(pts.iter().rev());
+    lower.pop();
+    upper.pop();
+    lower.extend(upper);
+    lower
+}
+
+fn on_segment<T: Coord>(p: &Point<T>, a: &Point<T>, b: &Point<T>) -> bool {
+    cross(a, b, p) == T::Wide::default()
+        && a.x.min(b.x) <= p.x
+        && p.x <= a.x.max(b.x)
+        && a.y.min(b.y) <= p.y
+        && p.y <= a.y.max(b.y)
+}
+
+/// Whether the closed segments `p1 p2` and `q1 q2` have a common point.
+pub fn segments_intersect<T: Coord>(
+    p1: &Point<T>,
+    p2: &Point<T>,
+    q1: &Point<T>,
+    q2: &Point<T>,
+) -> bool {
+    let d1 = orientation(q1, q2, p1);
+    let d2 = orientation(q1, q2, p2);
+    let d3 = orientation(p1, p2, q1);
+    let d4 = orientation(p1, p2, q2);
+    let proper = |a, b| {
+        matches!(
+            (a, b),
+            (Orientation::Clockwise, Orientation::CounterClockwise)
+                | (Orientation::CounterClockwise, Orientation::Clockwise)
+        )
+    };
+    (proper(d1, d2) && proper(d3, d4))
+        || on_segment(p1, q1, q2)
+        || on_segment(p2, q1, q2)
+        || on_segment(q1, p1, p2)
+        || on_segment(q2, p1, p2)
+}
+
+/// Where a point lies with respect to a polygon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Locates `p` with respect to the simple polygon with vertices `polygon`.
+pub fn locate<T: Coord>(polygon: &[Point<T>], p: &Point<T>) -> Location {
+    let zero = T::Wide::default();
+    let n = polygon.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (&polygon[i], &polygon[(i + 1) % n]);
+        if on_segment(p, a, b) {
+            return Location::Boundary;
+        }
+        // count the edges crossed by a ray going right from p
+        if (a.y > p.y) != (b.y > p.y) {
+            let side = cross(a, b, p);
+            if (b.y > a.y && side > zero) || (b.y < a.y && side < zero) {
+                inside = !inside;
+            }
+        }
+    }
+    if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+/// Axis-aligned rectangle, borders included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rect<T> {
+    pub min: Point<T>,
+    pub max: Point<T>,
+}
+
+impl<T: Coord> Rect<T> {
+    /// The rectangle with opposite corners `a` and `b`.
+    pub fn from_corners(a: &Point<T>, b: &Point<T>) -> Self {
+        Self {
+            min: Point::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    pub fn contains(&self, p: &Point<T>) -> bool {
+        self.min.x <= p.x && p.x <= self.max.x && self.min.y <= p.y && p.y <= self.max.y
+    }
+
+    pub fn contains_rect(&self, other: &Rect<T>) -> bool {
+        self.contains(&other.min) && self.contains(&other.max)
+    }
+
+    pub fn intersects(&self, other: &Rect<T>) -> bool {
+        self.min.x <= other.max.x
+            && other.min.x <= self.max.x
+            && self.min.y <= other.max.y
+            && other.min.y <= self.max.y
+    }
+}
+
+#[derive(PartialEq, Debug)]
+enum SegmentOrientation {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl SegmentOrientation {
+    fn from_points<T: Coord>(p1: &Point<T>, p2: &Point<T>) -> Self {
+        assert_ne!(p1, p2, "Cannot create orientation from identical points");
+        assert!(p1.x == p2.x || p1.y == p2.y, "Points share a common axis");
+        if p1.x == p2.x {
+            if p1.y < p2.y {
+                SegmentOrientation::Down
+            } else {
+                SegmentOrientation::Up
+            }
+        } else if p1.x < p2.x {
+            SegmentOrientation::Right
+        } else {
+            SegmentOrientation::Left
+        }
+    }
+}
+
+/// Tells whether rectangles of cells lie inside a rectilinear polygon of
+/// cells.
+///
+/// The polygon is given by the cells at its corners, in order, with each
+/// consecutive pair sharing a row or a column; the cells along its border
+/// are part of it. Works by tracing the outline just outside the border and
+/// checking that no side of it goes through the rectangle.
+pub struct InsideDetector<T> {
+    // for each x it gives the vertical edges at that x
+    vertical_bounds: BTreeMap<T, Vec<RangeInclusive<T>>>,
+    // for each y it gives the horizontal edges at that y
+    horizontal_bounds: BTreeMap<T, Vec<RangeInclusive<T>>>,
+}
+
+impl<T: Coord> InsideDetector<T> {
+    pub fn new(corners: &[Point<T>]) -> Self {
+        let outline = outline(corners);
+        let mut vertical_bounds: BTreeMap<T, Vec<RangeInclusive<T>>> = BTreeMap::new();
+        let mut horizontal_bounds: BTreeMap<T, Vec<RangeInclusive<T>>> = BTreeMap::new();
+        for (i, p1) in outline.iter().enumerate() {
+            let p2 = &outline[(i + 1) % outline.len()];
+            if p1.x == p2.x {
+                let range = p1.y.min(p2.y)..=p1.y.max(p2.y);
+                vertical_bounds.entry(p1.x).or_default().push(range);
+            }
+            if p1.y == p2.y {
+                let range = p1.x.min(p2.x)..=p1.x.max(p2.x);
+                horizontal_bounds.entry(p1.y).or_default().push(range);
+            }
+        }
+        Self {
+            vertical_bounds,
+            horizontal_bounds,
+        }
+    }
+
+    pub fn contains(&self, rect: &Rect<T>) -> bool {
+        let Rect { min, max } = rect;
+        //no collision with any vertical bound
+        self.vertical_bounds
+            .range((Included(min.x), Included(max.x)))
+            .flat_map(|(_, ranges)| ranges.iter())
+            .all(|r| max.y <= *r.start() || min.y >= *r.end())
+            // and no collision with any horizontal bound
+            && self
+                .horizontal_bounds
+                .range((Included(min.y), Included(max.y)))
+                .flat_map(|(_, ranges)| ranges.iter())
+                .all(|r| max.x <= *r.start() || min.x >= *r.end())
+    }
+}
+
+// The corners of the polygon that goes around `corners` one cell outside.
+fn outline<T: Coord>(corners: &[Point<T>]) -> Vec<Point<T>> {
+    let (zero, one) = (T::ZERO, T::ONE);
+    let up_left = Point::new(zero - one, zero - one);
+    let up_right = Point::new(one, zero - one);
+    let down_left = Point::new(zero - one, one);
+    let down_right = Point::new(one, one);
+
+    let mut exterior = Vec::with_capacity(corners.len());
+    let corners = exterior_on_the_left(corners);
+    exterior.push(corners[0] + up_left);
+    for w in corners.windows(3) {
+        let (p1, p2, p3) = (&w[0], &w[1], &w[2]);
+        // Invariant: Segment p1 p2 has its exterior on its left
+        let in_step = SegmentOrientation::from_points(p1, p2);
+        let out_step = SegmentOrientation::from_points(p2, p3);
+        let outside = match (in_step, out_step) {
+            (SegmentOrientation::Right, SegmentOrientation::Up) => up_left,
+            (SegmentOrientation::Right, SegmentOrientation::Down) => up_right,
+            (SegmentOrientation::Left, SegmentOrientation::Up) => down_left,
+            (SegmentOrientation::Left, SegmentOrientation::Down) => down_right,
+            (SegmentOrientation::Up, SegmentOrientation::Right) => up_left,
+            (SegmentOrientation::Up, SegmentOrientation::Left) => down_left,
+            (SegmentOrientation::Down, SegmentOrientation::Right) => up_right,
+            (SegmentOrientation::Down, SegmentOrientation::Left) => down_right,
+            _ => panic!("Line does not a zig-zag: {:?} {:?} {:?}", p1, p2, p3),
+        };
+        exterior.push(*p2 + outside);
+    }
+    exterior
+}
+
+fn exterior_on_the_left<T: Coord>(corners: &[Point<T>]) -> Vec<Point<T>> {
+    let mut corners = corners.to_vec();
+    // We start with a corner we know where its exterior is
+    let start = (0..corners.len())
+        .min_by_key(|&i| corners[i])
+        .expect("corners must not be empty to find start");
+    // We ensure that it will be the starting point of the tour
+    corners.rotate_left(start);
+    // It will also be the end point of the tour
+    corners.push(corners[0]);
+    // We ensure the first movement will be on the left
+    let first_step = SegmentOrientation::from_points(&corners[0], &corners[1]);
+    if first_step == SegmentOrientation::Down {
+        corners.reverse();
+    }
+    corners
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coords: &[(i32, i32)]) -> Vec<Point<i32>> {
+        coords.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    // an L shape, counter-clockwise with the y axis up
+    fn l_shape() -> Vec<Point<i32>> {
+        points(&[(0, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)])
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("7,-1".parse::<Point<i64>>().unwrap(), Point::new(7, -1));
+        let e = "7;1".parse::<Point<i32>>().unwrap_err();
+        assert_eq!(e.message, "Missing ','");
+    }
+
+    #[test]
+    fn test_convex_hull() {
+        let pts = points(&[(0, 0), (2, 0), (4, 0), (1, 1), (4, 4), (0, 4), (2, 3)]);
+        assert_eq!(convex_hull(&pts), points(&[(0, 0), (0, 4), (4, 4), (4, 0)]));
+    }
+
+    #[test]
+    fn test_area_and_orientation() {
+        let polygon = l_shape();
+        assert_eq!(signed_area2(&polygon), 24);
+        assert_eq!(polygon_orientation(&polygon), Orientation::CounterClockwise);
+        let reversed = polygon.into_iter().rev().collect::<Vec<_>>();
+        assert_eq!(signed_area2(&reversed), -24);
+        assert_eq!(polygon_orientation(&reversed), Orientation::Clockwise);
+        let (o, a) = (Point::new(0, 0), Point::new(2, 2));
+        assert_eq!(
+            orientation(&o, &a, &Point::new(4, 4)),
+            Orientation::Collinear
+        );
+    }
+
+    #[test]
+    fn test_locate() {
+        let polygon = l_shape();
+        assert_eq!(locate(&polygon, &Point::new(1, 3)), Location::Inside);
+        assert_eq!(locate(&polygon, &Point::new(3, 3)), Location::Outside);
+        assert_eq!(locate(&polygon, &Point::new(3, 2)), Location::Boundary);
+        assert_eq!(locate(&polygon, &Point::new(-1, 2)), Location::Outside);
+        assert_eq!(locate(&polygon, &Point::new(1, 2)), Location::Inside);
+    }
+
+    #[test]
+    fn test_segments_intersect() {
+        let p = |x, y| Point::new(x, y);
+        assert!(segments_intersect(&p(0, 0), &p(4, 4), &p(0, 4), &p(4, 0)));
+        assert!(segments_intersect(&p(0, 0), &p(4, 0), &p(2, 0), &p(2, 3)));
+        assert!(segments_intersect(&p(0, 0), &p(4, 0), &p(3, 0), &p(6, 0)));
+        assert!(!segments_intersect(&p(0, 0), &p(4, 0), &p(5, 0), &p(6, 0)));
+        assert!(!segments_intersect(&p(0, 0), &p(4, 4), &p(1, 0), &p(5, 4)));
+    }
+
+    #[test]
+    fn test_rect_containment() {
+        let r = Rect::from_corners(&Point::new(4, 1), &Point::new(0, 3));
+        assert_eq!((r.min, r.max), (Point::new(0, 1), Point::new(4, 3)));
+        assert!(r.contains(&Point::new(4, 3)));
+        assert!(!r.contains(&Point::new(5, 2)));
+        assert!(r.contains_rect(&Rect::from_corners(&Point::new(1, 1), &Point::new(3, 2))));
+        assert!(!r.contains_rect(&Rect::from_corners(&Point::new(1, 1), &Point::new(5, 2))));
+        assert!(r.intersects(&Rect::from_corners(&Point::new(4, 3), &Point::new(9, 9))));
+        assert!(!r.intersects(&Rect::from_corners(&Point::new(5, 0), &Point::new(9, 9))));
+    }
+
+    #[test]
+    fn test_inside_detector() {
+        // the example of 2025 day 9
+        let corners = points(&[
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ]);
+        let detector = InsideDetector::new(&corners);
+        let rect = |a: (i32, i32), b: (i32, i32)| {
+            Rect::from_corners(&Point::new(a.0, a.1), &Point::new(b.0, b.1))
+        };
+        assert!(detector.contains(&rect((9, 5), (2, 3))));
+        assert!(detector.contains(&rect((9, 7), (11, 1))));
+        assert!(!detector.contains(&rect((7, 1), (11, 7))));
+        assert!(!detector.contains(&rect((2, 5), (11, 1))));
+        assert!(!detector.contains(&rect((2, 3), (9, 7))));
+    }
+}
@@ -0,0 +1,437 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::collections::hash_map::Entry;
+use std::fmt::{Display, Write};
+use std::hash::Hash;
+
+/// Directed graph whose nodes are labels of type `N`.
+///
+/// Labels are interned: each one gets the index of its node (`0..len()`),
+/// which is what the algorithms take and return.
+#[derive(Clone, Debug)]
+pub struct Digraph<N> {
+    index: HashMap<N, usize>,
+    labels: Vec<N>,
+    outputs: Vec<Vec<usize>>,
+    inputs: Vec<Vec<usize>>,
+}
+
+impl<N> Default for Digraph<N> {
+    fn default() -> Self {
+        Self {
+            index: HashMap::new(),
+            labels: Vec::new(),
+            outputs: Vec::new(),
+            inputs: Vec::new(),
+        }
+    }
+}
+
+impl<N: Eq + Hash + Clone> Digraph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of the node labelled `label`, added if it is new.
+    pub fn intern(&mut self, label: N) -> usize {
+        let i = self.labels.len();
+        match self.index.entry(label) {
+            Entry::Occupied(o) => *o.get(),
+            Entry::Vacant(v) => {
+                self.labels.push(v.key().clone());
+                v.insert(i);
+                self.outputs.push(Vec::new());
+                self.inputs.push(Vec::new());
+                i
+            }
+        }
+    }
+
+    /// Adds the edge `from -> to`, and their nodes if they are new.
+    pub fn add_edge(&mut self, from: N, to: N) {
+        let from = self.intern(from);
+        let to = self.intern(to);
+        self.outputs[from].push(to);
+        self.inputs[to].push(from);
+    }
+}
+
+impl<N> Digraph<N> {
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.outputs.iter().map(Vec::len).sum()
+    }
+
+    pub fn index_of<Q>(&self, label: &Q) -> Option<usize>
+    where
+        N: Borrow<Q> + Eq + Hash,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.index.get(label).copied()
+    }
+
+    pub fn label(&self, node: usize) -> &N {
+        &self.labels[node]
+    }
+
+    pub fn successors(&self, node: usize) -> &[usize] {
+        &self.outputs[node]
+    }
+
+    pub fn predecessors(&self, node: usize) -> &[usize] {
+        &self.inputs[node]
+    }
+
+    /// Nodes in an order where every edge goes forward, or `None` if there
+    /// is a cycle.
+    pub fn toposort(&self) -> Option<Vec<usize>> {
+        let mut pending = self.inputs.iter().map(Vec::len).collect::<Vec<_>>();
+        let mut ready = (0..self.len())
+            .filter(|&i| pending[i] == 0)
+            .collect::<VecDeque<_>>();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = ready.pop_front() {
+            order.push(node);
+            for &next in &self.outputs[node] {
+                pending[next] -= 1;
+                if pending[next] == 0 {
+                    ready.push_back(next);
+                }
+            }
+        }
+        (order.len() == self.len()).then_some(order)
+    }
+
+    pub fn is_acyclic(&self) -> bool {
+        self.toposort().is_some()
+    }
+
+    /// The nodes of some cycle, in order, if there is any.
+    pub fn find_cycle(&self) -> Option<Vec<usize>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            OnPath,
+            Done,
+        }
+        let mut state = vec![State::New; self.len()];
+        for root in 0..self.len() {
+            if state[root] != State::New {
+                continue;
+            }
+            // iterative DFS: the current path with the next edge to follow
+            let mut path = vec![(root, 0)];
+            state[root] = State::OnPath;
+            while let Some(&mut (node, ref mut edge)) = path.last_mut() {
+                match self.outputs[node].get(*edge) {
+                    Some(&next) => {
+                        *edge += 1;
+                        match state[next] {
+                            State::New => {
+                                state[next] = State::OnPath;
+                                path.push((next, 0));
+                            }
+                            State::OnPath => {
+                                let start = path.iter().position(|&(n, _)| n == next).unwrap();
+                                return Some(path[start..].iter().map(|&(n, _)| n).collect());
+                            }
+                            State::Done => {}
+                        }
+                    }
+                    None => {
+                        state[node] = State::Done;
+                        path.pop();
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Strongly connected components (Kosaraju), each one with its nodes in
+    /// increasing order.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        // nodes by increasing finishing time of a DFS
+        let mut finished = Vec::with_capacity(self.len());
+        let mut visited = vec![false; self.len()];
+        for root in 0..self.len() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut path = vec![(root, 0)];
+            while let Some(&mut (node, ref mut edge)) = path.last_mut() {
+                match self.outputs[node].get(*edge) {
+                    Some(&next) => {
+                        *edge += 1;
+                        if !visited[next] {
+                            visited[next] = true;
+                            path.push((next, 0));
+                        }
+                    }
+                    None => {
+                        finished.push(node);
+                        path.pop();
+                    }
+                }
+            }
+        }
+        // the nodes reaching each root in the reversed graph form its component
+        let mut component = vec![usize::MAX; self.len()];
+        let mut components = Vec::new();
+        for &root in finished.iter().rev() {
+            if component[root] != usize::MAX {
+                continue;
+            }
+            let mut members = vec![root];
+            component[root] = components.len();
+            let mut stack = vec![root];
+            while let Some(node) = stack.pop() {
+                for &prev in &self.inputs[node] {
+                    if component[prev] == usize::MAX {
+                        component[prev] = components.len();
+                        members.push(prev);
+                        stack.push(prev);
+                    }
+                }
+            }
+            members.sort_unstable();
+            components.push(members);
+        }
+        components
+    }
+
+    /// For each node, whether it can be reached from `start`.
+    pub fn reachable_from(&self, start: usize) -> Vec<bool> {
+        let mut reached = vec![false; self.len()];
+        reached[start] = true;
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for &next in &self.outputs[node] {
+                if !reached[next] {
+                    reached[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        reached
+    }
+
+    pub fn can_reach(&self, from: usize, to: usize) -> bool {
+        self.reachable_from(from)[to]
+    }
+
+    /// Number of paths between every pair of nodes, or `None` if there is a
+    /// cycle (and so infinitely many).
+    ///
+    /// Counts grow exponentially with the depth of the graph: those that do
+    /// not fit in a `u64` saturate, and `PathCounts::get` tells them apart.
+    pub fn path_counts(&self) -> Option<PathCounts> {
+        let n = self.len();
+        let mut counts = vec![0u64; n * n];
+        // successors come later in the order, so they are done first
+        for &node in self.toposort()?.iter().rev() {
+            counts[node * n + node] = 1;
+            for &next in &self.outputs[node] {
+                for to in 0..n {
+                    counts[node * n + to] =
+                        counts[node * n + to].saturating_add(counts[next * n + to]);
+                }
+            }
+        }
+        Some(PathCounts { n, counts })
+    }
+
+    /// Number of paths from `from` to `to`, without the quadratic table of
+    /// `path_counts`; `None` if there is a cycle or too many paths to count.
+    pub fn count_paths(&self, from: usize, to: usize) -> Option<u64> {
+        let mut counts = vec![0u64; self.len()];
+        counts[to] = 1;
+        for &node in self.toposort()?.iter().rev() {
+            for &next in &self.outputs[node] {
+                counts[node] = counts[node].saturating_add(counts[next]);
+            }
+        }
+        Some(counts[from]).filter(|&count| count != u64::MAX)
+    }
+
+    /// The graph in Graphviz DOT format.
+    pub fn to_dot(&self) -> String
+    where
+        N: Display,
+    {
+        let mut dot = String::from("digraph {\n");
+        for (node, label) in self.labels.iter().enumerate() {
+            writeln!(
+                dot,
+                "    {} [label=\"{}\"];",
+                node,
+                escape_dot(&label.to_string())
+            )
+            .unwrap();
+        }
+        for (from, outputs) in self.outputs.iter().enumerate() {
+            for to in outputs {
+                writeln!(dot, "    {} -> {};", from, to).unwrap();
+            }
+        }
+        dot.push('}');
+        dot
+    }
+}
+
+// DOT strings only escape their quotes and backslashes; anything else,
+// non-ASCII or control characters included, stands for itself.
+fn escape_dot(label: &str) -> String {
+    let mut escaped = String::with_capacity(label.len());
+    for c in label.chars() {
+        if c == '"' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Number of paths between every pair of nodes of a DAG.
+#[derive(Clone, Debug)]
+pub struct PathCounts {
+    n: usize,
+    counts: Vec<u64>,
+}
+
+impl PathCounts {
+    /// Paths from `from` to `to`; one, the empty path, when they are equal.
+    /// `None` if there are too many to count, `u64::MAX` or more.
+    pub fn get(&self, from: usize, to: usize) -> Option<u64> {
+        Some(self.counts[from * self.n + to]).filter(|&count| count != u64::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&'static str, &'static str)]) -> Digraph<&'static str> {
+        let mut g = Digraph::new();
+        for &(from, to) in edges {
+            g.add_edge(from, to);
+        }
+        g
+    }
+
+    fn labels(g: &Digraph<&'static str>, nodes: &[usize]) -> Vec<&'static str> {
+        nodes.iter().map(|&i| *g.label(i)).collect()
+    }
+
+    #[test]
+    fn test_interning_and_adjacency() {
+        let mut g = graph(&[("a", "b"), ("a", "c"), ("c", "b")]);
+        assert_eq!(g.intern("c"), 2);
+        assert_eq!(g.intern("d"), 3);
+        assert_eq!((g.len(), g.edge_count()), (4, 3));
+        let b = g.index_of("b").unwrap();
+        assert_eq!(labels(&g, g.predecessors(b)), ["a", "c"]);
+        assert_eq!(labels(&g, g.successors(0)), ["b", "c"]);
+        assert_eq!(g.index_of("z"), None);
+    }
+
+    #[test]
+    fn test_toposort_and_cycles() {
+        let dag = graph(&[("a", "c"), ("b", "c"), ("c", "d"), ("a", "d")]);
+        let order = dag.toposort().unwrap();
+        assert_eq!(labels(&dag, &order), ["a", "b", "c", "d"]);
+        assert_eq!(dag.find_cycle(), None);
+
+        let cyclic = graph(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "b")]);
+        assert!(!cyclic.is_acyclic());
+        let cycle = cyclic.find_cycle().unwrap();
+        assert_eq!(labels(&cyclic, &cycle), ["b", "c", "d"]);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let g = graph(&[
+            ("a", "b"),
+            ("b", "a"),
+            ("b", "c"),
+            ("c", "d"),
+            ("d", "e"),
+            ("e", "c"),
+        ]);
+        let mut components = g
+            .strongly_connected_components()
+            .iter()
+            .map(|c| labels(&g, c))
+            .collect::<Vec<_>>();
+        components.sort();
+        assert_eq!(components, vec![vec!["a", "b"], vec!["c", "d", "e"]]);
+    }
+
+    #[test]
+    fn test_reachability_and_path_counts() {
+        let g = graph(&[("a", "b"), ("a", "c"), ("b", "d"), ("c", "d"), ("d", "e")]);
+        let [a, b, c, d, e] = ["a", "b", "c", "d", "e"].map(|l| g.index_of(l).unwrap());
+        assert!(g.can_reach(a, e));
+        assert!(!g.can_reach(b, c));
+        let paths = g.path_counts().unwrap();
+        assert_eq!(paths.get(a, e), Some(2));
+        assert_eq!(paths.get(b, e), Some(1));
+        assert_eq!(paths.get(e, a), Some(0));
+        assert_eq!(paths.get(d, d), Some(1));
+        assert_eq!(g.count_paths(a, e), Some(2));
+        assert_eq!(g.count_paths(e, a), Some(0));
+        assert_eq!(g.count_paths(d, d), Some(1));
+        let cycle = graph(&[("a", "b"), ("b", "a")]);
+        assert!(cycle.path_counts().is_none());
+        assert_eq!(cycle.count_paths(0, 1), None);
+    }
+
+    #[test]
+    fn test_path_counts_overflow() {
+        // layers of two nodes, each linked to both of the next layer: 2^(k-1)
+        // paths from a node to one k layers further
+        let mut g = Digraph::new();
+        for layer in 0..70 {
+            for (from, to) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+                g.add_edge((layer, from), (layer + 1, to));
+            }
+        }
+        let node = |layer, i| g.index_of(&(layer, i)).unwrap();
+        let paths = g.path_counts().unwrap();
+        assert_eq!(paths.get(node(0, 0), node(64, 1)), Some(1 << 63));
+        assert_eq!(paths.get(node(0, 0), node(65, 0)), None);
+        assert_eq!(paths.get(node(0, 1), node(70, 1)), None);
+        // pairs closer to each other are still counted
+        assert_eq!(paths.get(node(10, 0), node(70, 1)), Some(1 << 59));
+        assert_eq!(g.count_paths(node(0, 0), node(64, 1)), Some(1 << 63));
+        assert_eq!(g.count_paths(node(0, 0), node(65, 0)), None);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let g = graph(&[("a", "b\"")]);
+        assert_eq!(
+            g.to_dot(),
+            "digraph {\n    0 [label=\"a\"];\n    1 [label=\"b\\\"\"];\n    0 -> 1;\n}"
+        );
+    }
+
+    #[test]
+    fn test_to_dot_escapes_only_quotes_and_backslashes() {
+        let g = graph(&[("café\\", "tab\there")]);
+        assert_eq!(
+            g.to_dot(),
+            "digraph {\n    0 [label=\"café\\\\\"];\n    1 [label=\"tab\there\"];\n    0 -> 1;\n}"
+        );
+    }
+}
@@ -0,0 +1,315 @@
+use crate::ParseError;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// Offsets of the 4 orthogonal neighbors.
+pub const NEIGHBORS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the 8 neighbors (orthogonal and diagonal).
+pub const NEIGHBORS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+];
+
+/// A rectangular 2D map stored row by row in a single `Vec`.
+///
+/// Positions are `(x, y)`: `x` is the column and `y` the row, both starting
+/// at the top-left corner.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells, given row by row.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "cells do not fill the grid");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses a char map, one row per line, all rows of the same length.
+    pub fn from_lines<I, S>(lines: I) -> Result<Self, ParseError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+        T: TryFrom<char>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (y, line) in lines.into_iter().enumerate() {
+            let line = line.as_ref();
+            let before = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                let cell = T::try_from(c).map_err(|_| {
+                    ParseError::new(format!("Invalid cell {:?}", c))
+                        .at(x + 1, &c.to_string())
+                        .in_line(y + 1, line)
+                })?;
+                cells.push(cell);
+            }
+            let len = cells.len() - before;
+            if *width.get_or_insert(len) != len {
+                return Err(ParseError::new("Row of different width").in_line(y + 1, line));
+            }
+            height += 1;
+        }
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// The cell at `(x + dx, y + dy)`, if inside the grid.
+    pub fn get_offset(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<&T> {
+        let (x, y) = self.offset(x, y, dx, dy)?;
+        Some(&self[(x, y)])
+    }
+
+    /// The position `(x + dx, y + dy)`, if inside the grid.
+    pub fn offset(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx).filter(|&x| x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|&y| y < self.height)?;
+        Some((x, y))
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The orthogonal neighbors of `(x, y)` inside the grid.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbors(x, y, &NEIGHBORS4)
+    }
+
+    /// The orthogonal and diagonal neighbors of `(x, y)` inside the grid.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbors(x, y, &NEIGHBORS8)
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        offsets
+            .iter()
+            .filter_map(move |&(dx, dy)| self.offset(x, y, dx, dy))
+            .map(|pos| (pos, &self[pos]))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, but then there are no cells anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flatten().cloned().collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_right(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self
+            .columns()
+            .flat_map(|column| column.collect::<Vec<_>>().into_iter().rev())
+            .cloned()
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Rotates a quarter turn counter-clockwise.
+    pub fn rotate_left(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x))
+            .cloned()
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontally(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self
+            .rows()
+            .flat_map(|row| row.iter().rev())
+            .cloned()
+            .collect();
+        Self::new(self.width, self.height, cells)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) out of the grid", x, y))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) out of the grid", x, y))
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_lines(s.lines())
+    }
+}
+
+impl<T> fmt::Display for Grid<T>
+where
+    T: Copy,
+    char: From<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for &cell in row {
+                write!(f, "{}", char::from(cell))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(s: &str) -> Grid<u8> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let g = grid("ab\ncd\nef");
+        assert_eq!((g.width(), g.height()), (2, 3));
+        assert_eq!(g[(1, 2)], b'f');
+        assert_eq!(g.to_string(), "ab\ncd\nef");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = "ab\nc".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!(e.line, 2);
+        let e = "ab\ncλ".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "λ"));
+    }
+
+    #[test]
+    fn test_offsets() {
+        let g = grid("ab\ncd");
+        assert_eq!(g.get_offset(0, 0, 1, 1), Some(&b'd'));
+        assert_eq!(g.get_offset(0, 0, -1, 0), None);
+        assert_eq!(g.get_offset(1, 1, 0, 1), None);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let g = grid("abc\ndef\nghi");
+        let n4 = g.neighbors4(0, 0).map(|(_, &c)| c).collect::<Vec<_>>();
+        assert_eq!(n4, b"bd");
+        let mut n8 = g.neighbors8(1, 1).map(|(_, &c)| c).collect::<Vec<_>>();
+        n8.sort_unstable();
+        assert_eq!(n8, b"abcdfghi");
+    }
+
+    #[test]
+    fn test_views() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.row(1), b"def");
+        assert_eq!(g.column(2).copied().collect::<Vec<_>>(), b"cf");
+        assert_eq!(g.rows().count(), 2);
+        assert_eq!(g.columns().count(), 3);
+    }
+
+    #[test]
+    fn test_transformations() {
+        let g = grid("abc\ndef");
+        assert_eq!(g.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(g.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(g.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(g.flip_horizontally().to_string(), "cba\nfed");
+        assert_eq!(g.rotate_right().rotate_left(), g);
+    }
+}
@@ -0,0 +1,330 @@
+use crate::answers::DEFAULT_INPUT;
+use crate::{Error, Result};
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Environment variable with the directory holding the inputs.
+pub const DATA_DIR_VAR: &str = "AOC_DATA_DIR";
+
+/// Explicit path meaning "read the input from stdin".
+pub const STDIN: &str = "-";
+
+/// Where the input of a puzzle is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// Resolves the input called `name` (e.g. `day05.txt`), in order:
+    ///
+    /// 1. the `explicit` path, if any (`-` is stdin),
+    /// 2. `name` inside the directory given by `AOC_DATA_DIR`,
+    /// 3. `name` inside the `data` directory of the workspace.
+    pub fn resolve(name: &str, explicit: Option<&str>) -> Result<Source> {
+        Self::resolve_with(name, explicit, env::var_os(DATA_DIR_VAR))
+    }
+
+    fn resolve_with(
+        name: &str,
+        explicit: Option<&str>,
+        data_dir: Option<OsString>,
+    ) -> Result<Source> {
+        match (explicit, data_dir) {
+            (Some(STDIN), _) => Ok(Source::Stdin),
+            (Some(path), _) => Ok(Source::File(PathBuf::from(path))),
+            (None, Some(dir)) if !dir.is_empty() => Ok(Source::File(Path::new(&dir).join(name))),
+            (None, _) => Ok(Source::File(workspace_data_dir()?.join(name))),
+        }
+    }
+
+    /// How the source is shown in error messages.
+    pub fn display_name(&self) -> String {
+        match self {
+            Source::Stdin => "<stdin>".to_owned(),
+            Source::File(path) => path.display().to_string(),
+        }
+    }
+
+    pub fn read(&self) -> Result<String> {
+        let content = match self {
+            Source::Stdin => io::read_to_string(io::stdin()),
+            Source::File(path) => fs::read_to_string(path),
+        };
+        content.map_err(|source| Error::Io {
+            path: PathBuf::from(self.display_name()),
+            source,
+        })
+    }
+}
+
+/// An input loaded once, that hands out its lines and rows without copying
+/// them.
+///
+/// Lines do not include their line break, and rows are the same lines as
+/// bytes, so `byte(x, y)` is constant time where `chars().nth(x)` is not.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input {
+    text: String,
+    lines: Vec<Range<usize>>,
+}
+
+impl Input {
+    pub fn new(text: String) -> Self {
+        let lines = text
+            .lines()
+            .map(|line| {
+                let start = line.as_ptr() as usize - text.as_ptr() as usize;
+                start..start + line.len()
+            })
+            .collect();
+        Self { text, lines }
+    }
+
+    /// Loads the input called `name` (see `Source::resolve`).
+    pub fn load(name: &str) -> Result<Self> {
+        Ok(Self::new(Source::resolve(name, None)?.read()?))
+    }
+
+    /// The whole input, as read.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Number of lines.
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub fn line(&self, y: usize) -> &str {
+        &self.text[self.lines[y].clone()]
+    }
+
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &str> + ExactSizeIterator {
+        self.lines.iter().map(|range| &self.text[range.clone()])
+    }
+
+    pub fn row(&self, y: usize) -> &[u8] {
+        &self.text.as_bytes()[self.lines[y].clone()]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[u8]> + ExactSizeIterator {
+        self.lines
+            .iter()
+            .map(|range| &self.text.as_bytes()[range.clone()])
+    }
+
+    /// Length of the longest row.
+    pub fn width(&self) -> usize {
+        self.lines.iter().map(Range::len).max().unwrap_or(0)
+    }
+
+    /// The byte at column `x` of row `y`, if the row is long enough.
+    pub fn byte(&self, x: usize, y: usize) -> Option<u8> {
+        self.row(y).get(x).copied()
+    }
+
+    /// The bytes at column `x` of the rows `ys`, skipping the short ones.
+    pub fn column(&self, x: usize, ys: Range<usize>) -> impl Iterator<Item = u8> {
+        ys.filter_map(move |y| self.byte(x, y))
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Self::new(text.to_owned())
+    }
+}
+
+/// The directory inputs are read from by default: `AOC_DATA_DIR`, or else
+/// the `data` directory of the workspace.
+pub fn data_dir() -> Result<PathBuf> {
+    match env::var_os(DATA_DIR_VAR) {
+        Some(dir) if !dir.is_empty() => Ok(PathBuf::from(dir)),
+        _ => workspace_data_dir(),
+    }
+}
+
+/// Name of the default input of `day`, `dayNN.txt`.
+pub fn input_name(day: u32) -> String {
+    format!("day{:02}.txt", day)
+}
+
+/// Whether the input called `name` is there, for the tests that need it:
+/// if it is not they are skipped, saying so. Built with the
+/// `require-inputs` feature, a missing input panics instead.
+pub fn input_available(name: &str) -> bool {
+    let source = Source::resolve(name, None);
+    match &source {
+        Ok(Source::File(path)) if !path.exists() => {
+            missing(path, "skipping the test");
+            false
+        }
+        _ => true,
+    }
+}
+
+/// Reads the input called `name` or, if it is not there, stands in for it
+/// with `fallback`, an example or a generated input, so that the benches
+/// and `INPUT` statics still work on a fresh clone. Built with the
+/// `require-inputs` feature, a missing input panics instead.
+pub fn read_input_or(name: &str, fallback: impl FnOnce() -> String) -> String {
+    match crate::read_input(name, None) {
+        Ok(input) => input,
+        Err(Error::Io { path, source }) if source.kind() == io::ErrorKind::NotFound => {
+            missing(&path, "using a stand-in input");
+            fallback()
+        }
+        Err(e) => panic!("Failed to load input: {}", e),
+    }
+}
+
+fn missing(path: &Path, instead: &str) {
+    if cfg!(feature = "require-inputs") {
+        panic!("{} is missing, and the inputs are required", path.display());
+    }
+    // straight to stderr, which the test harness does not capture, so that
+    // the message shows for the tests that pass by skipping
+    let _ = writeln!(io::stderr(), "{} is missing, {}", path.display(), instead);
+}
+
+/// A named input of a day: `default` is `dayNN.txt`, and the inputs of
+/// the rest of the team go in `dayNN/<name>.txt`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputSet {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl InputSet {
+    pub fn read(&self) -> Result<String> {
+        Source::File(self.path.clone()).read()
+    }
+}
+
+impl fmt::Display for InputSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+/// The input sets of `day` there are in the data directory, `default`
+/// first and the rest by name. Sets that are not there are just left out.
+pub fn input_sets(day: u32) -> Vec<InputSet> {
+    data_dir()
+        .map(|dir| input_sets_in(&dir, day))
+        .unwrap_or_default()
+}
+
+fn input_sets_in(dir: &Path, day: u32) -> Vec<InputSet> {
+    let day_name = format!("day{:02}", day);
+    let default = dir.join(input_name(day));
+    let mut sets = fs::read_dir(dir.join(&day_name))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_stem()?.to_str()?.to_owned();
+            let is_input = path.extension().is_some_and(|e| e == "txt");
+            (is_input && path.is_file() && name != DEFAULT_INPUT).then_some(InputSet { name, path })
+        })
+        .collect::<Vec<_>>();
+    sets.sort_by(|a, b| a.name.cmp(&b.name));
+    if default.is_file() {
+        sets.insert(
+            0,
+            InputSet {
+                name: DEFAULT_INPUT.to_owned(),
+                path: default,
+            },
+        );
+    }
+    sets
+}
+
+// Last resort, only valid where the workspace was compiled.
+fn workspace_data_dir() -> Result<PathBuf> {
+    match Path::new(env!("CARGO_MANIFEST_DIR")).parent() {
+        None => Err(Error::MissingRootDir),
+        Some(parent) => Ok(parent.join("data")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explicit_path_wins() {
+        let source = Source::resolve_with("day01.txt", Some("mine.txt"), Some("dir".into()));
+        assert_eq!(source.unwrap(), Source::File(PathBuf::from("mine.txt")));
+        let source = Source::resolve_with("day01.txt", Some("-"), Some("dir".into()));
+        assert_eq!(source.unwrap(), Source::Stdin);
+    }
+
+    #[test]
+    fn test_data_dir_before_workspace() {
+        let source = Source::resolve_with("day01.txt", None, Some("/inputs".into()));
+        assert_eq!(
+            source.unwrap(),
+            Source::File(PathBuf::from("/inputs/day01.txt"))
+        );
+    }
+
+    #[test]
+    fn test_input_lines_rows_and_columns() {
+        let input = Input::from("12 3\r\n 45\n\n6");
+        assert_eq!(input.len(), 4);
+        assert_eq!(input.lines().collect::<Vec<_>>(), ["12 3", " 45", "", "6"]);
+        assert_eq!(input.row(1), b" 45");
+        assert_eq!(input.width(), 4);
+        assert_eq!(input.byte(2, 1), Some(b'5'));
+        assert_eq!(input.byte(3, 1), None);
+        assert_eq!(input.column(0, 0..4).collect::<Vec<_>>(), b"1 6");
+    }
+
+    #[test]
+    fn test_input_sets() {
+        let dir = env::temp_dir().join(format!("common-sets-{}", std::process::id()));
+        fs::create_dir_all(dir.join("day05")).unwrap();
+        assert_eq!(input_sets_in(&dir, 5), []);
+        for file in [
+            "day05.txt",
+            "day05/zoe.txt",
+            "day05/ada.txt",
+            "day05/notes.md",
+        ] {
+            fs::write(dir.join(file), "1\n").unwrap();
+        }
+        let names = input_sets_in(&dir, 5)
+            .into_iter()
+            .map(|set| set.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["default", "ada", "zoe"]);
+        assert_eq!(input_sets_in(&dir, 6), []);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_workspace_default() {
+        let source = Source::resolve_with("day01.txt", None, None).unwrap();
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        assert_eq!(source, Source::File(root.join("data/day01.txt")));
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+
+/// Integers usable as interval bounds.
+pub trait Bound: Copy + Ord + Debug {
+    fn succ(self) -> Option<Self>;
+    fn pred(self) -> Option<Self>;
+    /// Number of values in `start..=end` (`start <= end`).
+    fn span(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),*) => {
+        $(
+            impl Bound for $t {
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn span(start: Self, end: Self) -> u128 {
+                    (end as i128 - start as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_bound!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// A set of integers stored as sorted, disjoint and non-adjacent inclusive
+/// ranges.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    // sorted by start, and each range ends at least 2 before the next starts
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Adds every value of `range`, merging it with the ones it overlaps or
+    /// touches.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // first range that ends at or after start - 1
+        let first = self
+            .ranges
+            .partition_point(|&(_, e)| e.succ().is_some_and(|e| e < start));
+        // first range that starts after end + 1
+        let last = self
+            .ranges
+            .partition_point(|&(s, _)| end.succ().is_none_or(|end| s <= end));
+        if first < last {
+            start = start.min(self.ranges[first].0);
+            end = end.max(self.ranges[last - 1].1);
+        }
+        self.ranges.splice(first..last, [(start, end)]);
+    }
+
+    /// Takes out every value of `range`, splitting the ranges it cuts.
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self.ranges.partition_point(|&(_, e)| e < start);
+        let last = self.ranges.partition_point(|&(s, _)| s <= end);
+        if first >= last {
+            return;
+        }
+        let mut kept = Vec::with_capacity(2);
+        let (s, _) = self.ranges[first];
+        if s < start {
+            kept.push((s, start.pred().unwrap()));
+        }
+        let (_, e) = self.ranges[last - 1];
+        if end < e {
+            kept.push((end.succ().unwrap(), e));
+        }
+        self.ranges.splice(first..last, kept);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|&(_, e)| e < value);
+        self.ranges.get(i).is_some_and(|&(s, _)| s <= value)
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> u128 {
+        self.ranges.iter().map(|&(s, e)| T::span(s, e)).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The maximal ranges of the set, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|&(s, e)| s..=e)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.insert(range);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (s1, e1) = self.ranges[i];
+            let (s2, e2) = other.ranges[j];
+            let (s, e) = (s1.max(s2), e1.min(e2));
+            if s <= e {
+                ranges.push((s, e));
+            }
+            // the range that ends first cannot meet anything else
+            if e1 < e2 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.remove(range);
+        }
+        result
+    }
+
+    /// The values of `bounds` that are not in the set.
+    pub fn gaps(&self, bounds: RangeInclusive<T>) -> Self {
+        let mut result = Self::new();
+        result.insert(bounds);
+        result.difference(self)
+    }
+}
+
+impl<T: Bound> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut ranges = iter
+            .into_iter()
+            .map(RangeInclusive::into_inner)
+            .filter(|(s, e)| s <= e)
+            .collect::<Vec<_>>();
+        ranges.sort_unstable();
+        // merge in a single pass instead of inserting one by one
+        let mut merged: Vec<(T, T)> = Vec::with_capacity(ranges.len());
+        for (s, e) in ranges {
+            match merged.last_mut() {
+                Some(last) if last.1.succ().is_none_or(|next| s <= next) => {
+                    last.1 = last.1.max(e);
+                }
+                _ => merged.push((s, e)),
+            }
+        }
+        Self { ranges: merged }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[(i32, i32)]) -> IntervalSet<i32> {
+        ranges.iter().map(|&(s, e)| s..=e).collect()
+    }
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<(i32, i32)> {
+        set.iter().map(RangeInclusive::into_inner).collect()
+    }
+
+    #[test]
+    fn test_insert_merges_overlapping_and_adjacent() {
+        let mut s = IntervalSet::new();
+        s.insert(10..=14);
+        s.insert(3..=5);
+        s.insert(16..=20);
+        assert_eq!(ranges(&s), [(3, 5), (10, 14), (16, 20)]);
+        s.insert(12..=18);
+        assert_eq!(ranges(&s), [(3, 5), (10, 20)]);
+        s.insert(6..=9);
+        assert_eq!(ranges(&s), [(3, 20)]);
+    }
+
+    #[test]
+    fn test_collect_is_like_inserting() {
+        let collected = set(&[(16, 20), (3, 5), (12, 18), (10, 14), (22, 22), (21, 21)]);
+        assert_eq!(ranges(&collected), [(3, 5), (10, 22)]);
+    }
+
+    #[test]
+    fn test_remove_splits() {
+        let mut s = set(&[(0, 10), (20, 30)]);
+        s.remove(5..=24);
+        assert_eq!(ranges(&s), [(0, 4), (25, 30)]);
+        s.remove(0..=0);
+        s.remove(30..=40);
+        assert_eq!(ranges(&s), [(1, 4), (25, 29)]);
+    }
+
+    #[test]
+    fn test_membership_and_len() {
+        let s = set(&[(3, 5), (10, 14), (16, 20), (12, 18)]);
+        assert!([3, 5, 11, 17, 20].iter().all(|&x| s.contains(x)));
+        assert!([1, 8, 21, 32].iter().all(|&x| !s.contains(x)));
+        assert_eq!(s.len(), 14);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (40, 50)]);
+        assert_eq!(ranges(&a.union(&b)), [(0, 30), (40, 50)]);
+        assert_eq!(ranges(&a.intersection(&b)), [(5, 10), (20, 25)]);
+        assert_eq!(ranges(&a.difference(&b)), [(0, 4), (26, 30)]);
+    }
+
+    #[test]
+    fn test_gaps() {
+        let s = set(&[(3, 5), (10, 14)]);
+        assert_eq!(ranges(&s.gaps(0..=20)), [(0, 2), (6, 9), (15, 20)]);
+        assert_eq!(ranges(&s.gaps(4..=12)), [(6, 9)]);
+    }
+
+    #[test]
+    fn test_extreme_bounds() {
+        let mut s = IntervalSet::new();
+        s.insert(u64::MAX - 1..=u64::MAX);
+        s.insert(0..=u64::MAX - 2);
+        assert_eq!(s.len(), u64::MAX as u128 + 1);
+        s.remove(0..=u64::MAX);
+        assert!(s.is_empty());
+    }
+}
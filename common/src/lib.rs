@@ -1,38 +1,61 @@
-use std::error::Error;
-use std::fmt::Debug;
-use std::fs;
-use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-pub fn read_file_as_string(path: &str) -> Result<String, Box<dyn Error>> {
-    let path = get_path_from_root(path)?;
-    let input = fs::read_to_string(path)?;
+pub mod answers;
+mod bench;
+pub mod dsu;
+pub mod error;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod intervals;
+pub mod parse;
+pub mod solution;
+pub mod variant;
+
+pub use answers::{Answers, Check, check_answer, check_input_sets, check_variants};
+pub use error::{Error, ParseError, Result};
+pub use input::{Input, InputSet, Source, input_available, input_sets, read_input_or};
+pub use solution::{Answer, Day, Parsed, Solution};
+pub use variant::{Variant, VariantId};
+
+/// Reads the input called `name` (e.g. `day05.txt`) from the first
+/// available source (see `Source::resolve`).
+pub fn read_input(name: &str, explicit: Option<&str>) -> Result<String> {
+    Source::resolve(name, explicit)?.read()
+}
+
+pub fn read_file_as_string(name: &str) -> Result<String> {
+    let input = read_input(name, None)?;
     Ok(input.trim().to_owned())
 }
 
-pub fn read_file_as_lines(path: &str) -> Result<Vec<String>, Box<dyn Error>> {
-    let path = get_path_from_root(path)?;
-    let content = fs::read_to_string(path)?;
+pub fn read_file_as_lines(name: &str) -> Result<Vec<String>> {
+    let content = read_input(name, None)?;
     Ok(content.lines().map(str::to_owned).collect())
 }
 
-pub fn read_file_as_elements<T>(path: &str) -> Result<Vec<T>, Box<dyn Error>>
+pub fn read_file_as_elements<T>(name: &str) -> Result<Vec<T>>
 where
     T: FromStr,
-    <T as FromStr>::Err: Debug,
+    <T as FromStr>::Err: Into<ParseError>,
 {
-    let path = get_path_from_root(path)?;
-    let content = fs::read_to_string(path)?;
-    content.lines()
-        .map(|s| s.parse()
-            .map_err(|e| Box::<dyn Error>::from(format!("{:?}", e))))
-        .collect()
+    let source = Source::resolve(name, None)?;
+    let content = source.read()?;
+    parse_lines(&content).map_err(|e| e.in_file(&source.display_name()).into())
 }
-
-fn get_path_from_root(path: &str) -> Result<PathBuf, String> {
-    match Path::new(env!("CARGO_MANIFEST_DIR")).parent() {
-        None => Err("Unable to get to root dir".to_owned()),
-        Some(parent) => Ok(parent.join(path)),
-    }
+/// Parses every line of `input` as a `T`, locating the first failure.
+pub fn parse_lines<T>(input: &str) -> std::result::Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse()
+                .map_err(|e: T::Err| e.into().in_line(i + 1, line))
+        })
+        .collect()
 }
-
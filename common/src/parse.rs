@@ -0,0 +1,442 @@
+//! Small parser combinators for puzzle inputs.
+//!
+//! A parser is anything implementing [`Parser`], closures over a [`Cursor`]
+//! included, so ad hoc grammar pieces mix freely with the ones below.
+//! Errors are located at the line and column where parsing failed.
+//!
+//! A parser that fails without consuming input lets [`many`], [`lines`] and
+//! [`sections`] stop there; once it has consumed some input its error is
+//! final. Only [`alt`] backtracks.
+
+use crate::ParseError;
+use std::str::FromStr;
+
+/// Position in the text being parsed.
+#[derive(Clone, Copy, Debug)]
+pub struct Cursor<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text, offset: 0 }
+    }
+
+    /// Byte offset from the start of the text.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.offset == self.text.len()
+    }
+
+    fn is_at_end_of_line(&self) -> bool {
+        let rest = self.rest();
+        rest.is_empty() || rest.starts_with('\n') || rest.starts_with("\r\n")
+    }
+
+    fn advance(&mut self, len: usize) -> &'a str {
+        let consumed = &self.text[self.offset..self.offset + len];
+        self.offset += len;
+        consumed
+    }
+
+    fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let len = rest.find(|c| !pred(c)).unwrap_or(rest.len());
+        self.advance(len)
+    }
+
+    /// An error at the current position, blaming the word found there.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        let rest = self.rest();
+        let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        self.error_at(self.offset, len, message)
+    }
+
+    /// An error blaming the `len` bytes at `offset`.
+    pub fn error_at(&self, offset: usize, len: usize, message: impl Into<String>) -> ParseError {
+        self.locate(ParseError::new(message), offset, len)
+    }
+
+    fn locate(&self, e: ParseError, offset: usize, len: usize) -> ParseError {
+        let before = &self.text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.text[offset..]
+            .find('\n')
+            .map_or(self.text.len(), |i| offset + i);
+        let line = before.matches('\n').count() + 1;
+        let column = before[line_start..].chars().count() + 1;
+        e.at(column, &self.text[offset..offset + len])
+            .in_line(line, self.text[line_start..line_end].trim_end_matches('\r'))
+    }
+}
+
+pub trait Parser<'a, T> {
+    fn parse(&self, cursor: &mut Cursor<'a>) -> Result<T, ParseError>;
+
+    fn map<U>(self, f: impl Fn(T) -> U) -> impl Parser<'a, U>
+    where
+        Self: Sized,
+    {
+        move |c: &mut Cursor<'a>| self.parse(c).map(&f)
+    }
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(&mut Cursor<'a>) -> Result<T, ParseError>,
+{
+    fn parse(&self, cursor: &mut Cursor<'a>) -> Result<T, ParseError> {
+        self(cursor)
+    }
+}
+
+// A tuple of parsers runs them in sequence.
+macro_rules! impl_sequence {
+    ($($p:ident: $t:ident),+) => {
+        impl<'a, $($t, $p: Parser<'a, $t>),+> Parser<'a, ($($t,)+)> for ($($p,)+) {
+            #[allow(non_snake_case)]
+            fn parse(&self, cursor: &mut Cursor<'a>) -> Result<($($t,)+), ParseError> {
+                let ($($p,)+) = self;
+                Ok(($($p.parse(cursor)?,)+))
+            }
+        }
+    };
+}
+
+impl_sequence!(A: TA, B: TB);
+impl_sequence!(A: TA, B: TB, C: TC);
+impl_sequence!(A: TA, B: TB, C: TC, D: TD);
+
+/// Parses the whole `text`, but for trailing whitespace.
+pub fn complete<'a, T>(text: &'a str, parser: impl Parser<'a, T>) -> Result<T, ParseError> {
+    let mut cursor = Cursor::new(text);
+    let value = parser.parse(&mut cursor)?;
+    cursor.take_while(char::is_whitespace);
+    if cursor.is_at_end() {
+        Ok(value)
+    } else {
+        Err(cursor.error("Unexpected text"))
+    }
+}
+
+pub fn tag<'a>(expected: &'static str) -> impl Parser<'a, &'a str> {
+    move |c: &mut Cursor<'a>| {
+        if c.rest().starts_with(expected) {
+            Ok(c.advance(expected.len()))
+        } else {
+            Err(c.error(format!("Expected {:?}", expected)))
+        }
+    }
+}
+
+/// One or more chars satisfying `pred`.
+pub fn take_while1<'a>(pred: impl Fn(char) -> bool) -> impl Parser<'a, &'a str> {
+    move |c: &mut Cursor<'a>| match c.take_while(&pred) {
+        "" => Err(c.error("Unexpected character")),
+        taken => Ok(taken),
+    }
+}
+
+/// Decimal digits, converted to `T`.
+pub fn unsigned<'a, T>() -> impl Parser<'a, T>
+where
+    T: FromStr,
+    T::Err: Into<ParseError>,
+{
+    move |c: &mut Cursor<'a>| number(c, 0)
+}
+
+/// Decimal digits with an optional sign, converted to `T`.
+pub fn signed<'a, T>() -> impl Parser<'a, T>
+where
+    T: FromStr,
+    T::Err: Into<ParseError>,
+{
+    move |c: &mut Cursor<'a>| {
+        let sign = usize::from(c.rest().starts_with(['-', '+']));
+        number(c, sign)
+    }
+}
+
+fn number<T>(c: &mut Cursor<'_>, sign: usize) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Into<ParseError>,
+{
+    let digits = c.rest()[sign..]
+        .find(|ch: char| !ch.is_ascii_digit())
+        .unwrap_or(c.rest().len() - sign);
+    if digits == 0 {
+        return Err(c.error("Expected a number"));
+    }
+    let start = c.offset;
+    c.advance(sign + digits)
+        .parse()
+        .map_err(|e: T::Err| c.locate(e.into(), start, sign + digits))
+}
+
+/// Zero or more spaces or tabs.
+pub fn spaces<'a>() -> impl Parser<'a, ()> {
+    move |c: &mut Cursor<'a>| {
+        c.take_while(|ch| ch == ' ' || ch == '\t');
+        Ok(())
+    }
+}
+
+/// One or more spaces or tabs.
+pub fn spaces1<'a>() -> impl Parser<'a, ()> {
+    take_while1(|ch| ch == ' ' || ch == '\t').map(|_| ())
+}
+
+pub fn newline<'a>() -> impl Parser<'a, ()> {
+    move |c: &mut Cursor<'a>| {
+        if c.rest().starts_with('\n') {
+            c.advance(1);
+        } else if c.rest().starts_with("\r\n") {
+            c.advance(2);
+        } else {
+            return Err(c.error("Expected end of line"));
+        }
+        Ok(())
+    }
+}
+
+/// A line break followed by one or more blank lines.
+pub fn blank_lines<'a>() -> impl Parser<'a, ()> {
+    move |c: &mut Cursor<'a>| {
+        newline().parse(c)?;
+        let mut blank = 0;
+        loop {
+            let mut next = *c;
+            spaces().parse(&mut next)?;
+            if newline().parse(&mut next).is_err() {
+                break;
+            }
+            *c = next;
+            blank += 1;
+        }
+        if blank == 0 {
+            return Err(c.error("Expected a blank line"));
+        }
+        Ok(())
+    }
+}
+
+pub fn preceded<'a, A, T>(
+    before: impl Parser<'a, A>,
+    parser: impl Parser<'a, T>,
+) -> impl Parser<'a, T> {
+    move |c: &mut Cursor<'a>| {
+        before.parse(c)?;
+        parser.parse(c)
+    }
+}
+
+pub fn terminated<'a, T, B>(
+    parser: impl Parser<'a, T>,
+    after: impl Parser<'a, B>,
+) -> impl Parser<'a, T> {
+    move |c: &mut Cursor<'a>| {
+        let value = parser.parse(c)?;
+        after.parse(c)?;
+        Ok(value)
+    }
+}
+
+pub fn separated_pair<'a, A, S, B>(
+    first: impl Parser<'a, A>,
+    separator: impl Parser<'a, S>,
+    second: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |c: &mut Cursor<'a>| {
+        let a = first.parse(c)?;
+        separator.parse(c)?;
+        Ok((a, second.parse(c)?))
+    }
+}
+
+/// `inner` between `open` and `close`, as in `(1,3)` or `[.##.]`.
+pub fn bracketed<'a, T>(
+    open: &'static str,
+    inner: impl Parser<'a, T>,
+    close: &'static str,
+) -> impl Parser<'a, T> {
+    preceded(tag(open), terminated(inner, tag(close)))
+}
+
+/// `key`, then `separator` with optional spaces around it, then `value`.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<'a, K>,
+    separator: &'static str,
+    value: impl Parser<'a, V>,
+) -> impl Parser<'a, (K, V)> {
+    separated_pair(
+        key,
+        preceded(spaces(), terminated(tag(separator), spaces())),
+        value,
+    )
+}
+
+/// One or more `item`s with a `separator` between each two.
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |c: &mut Cursor<'a>| {
+        let mut items = vec![item.parse(c)?];
+        loop {
+            let start = c.offset;
+            match separator.parse(c) {
+                Ok(_) => items.push(item.parse(c)?),
+                Err(_) if c.offset == start => return Ok(items),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Zero or more `item`s, up to the first one that fails without consuming.
+pub fn many<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |c: &mut Cursor<'a>| {
+        let mut items = Vec::new();
+        loop {
+            let start = c.offset;
+            match item.parse(c) {
+                Ok(value) => items.push(value),
+                Err(_) if c.offset == start => return Ok(items),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// `first`, or else `second` from the same position.
+///
+/// When both fail, the error that got further wins.
+pub fn alt<'a, T>(first: impl Parser<'a, T>, second: impl Parser<'a, T>) -> impl Parser<'a, T> {
+    move |c: &mut Cursor<'a>| {
+        let start = *c;
+        let e1 = match first.parse(c) {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+        *c = start;
+        second.parse(c).map_err(|e2| {
+            if (e1.line, e1.column) > (e2.line, e2.column) {
+                e1
+            } else {
+                e2
+            }
+        })
+    }
+}
+
+/// Consecutive lines, each one entirely parsed by `item`.
+///
+/// Stops at a blank line or at the end of the input.
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |c: &mut Cursor<'a>| {
+        let mut items = Vec::new();
+        loop {
+            items.push(item.parse(c)?);
+            if !c.is_at_end_of_line() {
+                return Err(c.error("Unexpected text"));
+            }
+            let mut next = *c;
+            if newline().parse(&mut next).is_err()
+                || next.rest().lines().next().is_none_or(is_blank)
+            {
+                return Ok(items);
+            }
+            *c = next;
+        }
+    }
+}
+
+/// Groups of lines separated by blank lines, each one parsed by `item`.
+pub fn sections<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |c: &mut Cursor<'a>| {
+        let mut items = vec![item.parse(c)?];
+        loop {
+            let mut next = *c;
+            if blank_lines().parse(&mut next).is_err() || next.rest().trim().is_empty() {
+                return Ok(items);
+            }
+            *c = next;
+            items.push(item.parse(c)?);
+        }
+    }
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_and_lists() {
+        let list = separated(signed::<i32>(), tag(","));
+        assert_eq!(complete("12,-3,+4", list).unwrap(), vec![12, -3, 4]);
+        let groups = many(preceded(
+            spaces(),
+            bracketed("(", separated(unsigned::<u8>(), tag(",")), ")"),
+        ));
+        assert_eq!(
+            complete(" (1,3) (2)", groups).unwrap(),
+            vec![vec![1, 3], vec![2]]
+        );
+    }
+
+    #[test]
+    fn test_key_value_lines_and_sections() {
+        let entry = key_value(
+            take_while1(char::is_alphabetic),
+            ":",
+            separated(unsigned::<u32>(), spaces1()),
+        );
+        let input = "a: 1 2\nb : 3\n\n\nc:4\n";
+        let parsed = complete(input, sections(lines(entry))).unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                vec![("a", vec![1, 2]), ("b", vec![3])],
+                vec![("c", vec![4])]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_alt_backtracks() {
+        let either = || {
+            alt(
+                terminated(unsigned::<u32>(), tag(":")).map(|n| (n, n)),
+                separated_pair(unsigned::<u32>(), tag("x"), unsigned::<u32>()),
+            )
+        };
+        assert_eq!(complete("4:", either()).unwrap(), (4, 4));
+        assert_eq!(complete("4x5", either()).unwrap(), (4, 5));
+    }
+
+    #[test]
+    fn test_error_positions() {
+        let grid = || lines(separated(unsigned::<u8>(), tag(" ")));
+        let e = complete("1 2\n3 x4", grid()).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 3, "x4"));
+        let e = complete("1 2\n3 4000", grid()).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 3, "4000"));
+        assert_eq!(e.message, "number too large to fit in target type");
+        let e = complete("1 2\n\n3", grid()).unwrap_err();
+        assert_eq!((e.line, e.text.as_str()), (3, "3"));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::error::ParseError;
+use crate::variant::{Variant, VariantId};
+use std::env;
+use std::error::Error;
+
+/// Answer of a part, rendered for display, or the reason it could not be computed.
+pub type Answer = Result<String, Box<dyn Error>>;
+
+/// A day of the calendar, split in its phases: parsing the input into the
+/// model of the day, then solving each part from the model, so that they
+/// can be timed apart.
+///
+/// Every `dayNN` crate exposes a unit struct implementing this trait, which
+/// makes it a `Solution` for the runner.
+pub trait Day: Sync {
+    /// What the input is parsed into, owning its data.
+    type Model: 'static;
+
+    /// Day of the calendar (1-based).
+    fn day(&self) -> u32;
+
+    /// Puzzle title, as shown on the website.
+    fn title(&self) -> &'static str;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError>;
+
+    fn part1(&self, model: &Self::Model) -> Answer;
+
+    /// Days without a second puzzle (the last one) keep the default.
+    fn part2(&self, _model: &Self::Model) -> Option<Answer> {
+        None
+    }
+
+    /// Alternative implementations of the parts, checked against them by the
+    /// runner and the tests (see `variant`).
+    fn variants(&self) -> &'static [Variant<Self::Model>] {
+        &[]
+    }
+}
+
+/// A day of the calendar, as seen by the workspace runner.
+///
+/// It is implemented by every `Day`, so the runner can solve any day from
+/// its raw input without knowing its model.
+pub trait Solution: Sync {
+    /// Day of the calendar (1-based).
+    fn day(&self) -> u32;
+
+    /// Puzzle title, as shown on the website.
+    fn title(&self) -> &'static str;
+
+    /// Parses `input` into the model of the day, ready to solve the parts.
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn Parsed + 'a>, ParseError>;
+
+    /// The variants of the day, solved with `Parsed::variant`.
+    fn variants(&self) -> Vec<VariantId>;
+
+    /// Parses `input` and solves part 1 from it.
+    fn part1(&self, input: &str) -> Answer {
+        self.parse(input)?.part1()
+    }
+
+    /// Parses `input` and solves part 2 from it, if the day has one.
+    fn part2(&self, input: &str) -> Option<Answer> {
+        match self.parse(input) {
+            Ok(parsed) => parsed.part2(),
+            Err(e) => Some(Err(e.into())),
+        }
+    }
+
+    /// Name of the input inside the data directory.
+    fn input_name(&self) -> String {
+        crate::input::input_name(self.day())
+    }
+}
+
+/// An input parsed by a `Solution`, which its parts are solved from.
+pub trait Parsed {
+    fn part1(&self) -> Answer;
+
+    fn part2(&self) -> Option<Answer>;
+
+    /// Solves the part of `variant`, one of `Solution::variants`, with it.
+    fn variant(&self, variant: VariantId) -> Answer;
+}
+
+struct Model<'a, D: Day> {
+    day: &'a D,
+    model: D::Model,
+}
+
+impl<D: Day> Parsed for Model<'_, D> {
+    fn part1(&self) -> Answer {
+        self.day.part1(&self.model)
+    }
+
+    fn part2(&self) -> Option<Answer> {
+        self.day.part2(&self.model)
+    }
+
+    fn variant(&self, variant: VariantId) -> Answer {
+        let found = self.day.variants().iter().find(|v| v.id == variant);
+        match found {
+            Some(found) => found.solve(&self.model),
+            None => Err(format!("day {} has no variant {}", self.day.day(), variant).into()),
+        }
+    }
+}
+
+impl<D: Day> Solution for D {
+    fn day(&self) -> u32 {
+        Day::day(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Day::title(self)
+    }
+
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn Parsed + 'a>, ParseError> {
+        let model = Day::parse(self, input)?;
+        Ok(Box::new(Model { day: self, model }))
+    }
+
+    fn variants(&self) -> Vec<VariantId> {
+        Day::variants(self).iter().map(|v| v.id).collect()
+    }
+}
+
+/// Entry point shared by the `dayNN` binaries: solves both parts of the
+/// input given as first argument (`-` for stdin), or of the default one.
+pub fn run(solution: &dyn Solution) -> Result<(), Box<dyn Error>> {
+    let explicit = env::args().nth(1);
+    let input = crate::read_input(&solution.input_name(), explicit.as_deref())?;
+    let parsed = solution.parse(&input)?;
+    println!("Part1: {}", parsed.part1()?);
+    if let Some(answer) = parsed.part2() {
+        println!("Part2: {}", answer?);
+    }
+    Ok(())
+}
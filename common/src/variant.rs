@@ -0,0 +1,162 @@
+//! Alternative implementations of the parts of a day, registered through
+//! `Day::variants` so that they are checked against the answers of
+//! `Day::part1` and `Day::part2` and benched along with them, rather than
+//! each wired into the tests and benches by hand:
+//!
+//! ```ignore
+//! static VARIANTS: &[Variant<Input>] = &[
+//!     Variant::new("iterative", 1, |input| Ok(part1_iterative(input).to_string())),
+//! ];
+//! ```
+
+use crate::solution::{Answer, Parsed, Solution};
+use std::fmt;
+
+/// Which variant: its name and the part it solves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VariantId {
+    pub name: &'static str,
+    pub part: u32,
+}
+
+impl VariantId {
+    /// Compares `answer`, given by this variant, with `expected`, the one of
+    /// its part.
+    pub fn agree(&self, answer: Answer, expected: &str) -> Result<String, String> {
+        match answer {
+            Ok(answer) if answer == expected => Ok(answer),
+            Ok(answer) => Err(format!(
+                "{} disagrees: got {}, but part{} got {}",
+                self, answer, self.part, expected
+            )),
+            Err(e) => Err(format!("{}: {}", self, e)),
+        }
+    }
+}
+
+/// Named as the functions they usually wrap, e.g. `part2_strings`.
+impl fmt::Display for VariantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "part{}_{}", self.part, self.name)
+    }
+}
+
+/// An alternative implementation of a part, solving it from the model `M`
+/// of the day.
+pub struct Variant<M> {
+    pub id: VariantId,
+    solve: fn(&M) -> Answer,
+}
+
+impl<M> Variant<M> {
+    /// Panics if `part` is not 1 or 2, at compile time in a `static`.
+    pub const fn new(name: &'static str, part: u32, solve: fn(&M) -> Answer) -> Self {
+        assert!(part == 1 || part == 2, "a variant solves part 1 or 2");
+        Self {
+            id: VariantId { name, part },
+            solve,
+        }
+    }
+
+    pub fn solve(&self, model: &M) -> Answer {
+        (self.solve)(model)
+    }
+}
+
+impl<M> fmt::Display for Variant<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.id.fmt(f)
+    }
+}
+
+/// Solves `parsed` with every variant of `solution`, and describes those
+/// that fail or disagree with their part. Parts that fail themselves have
+/// nothing to compare with, so their variants are left out.
+pub fn disagreements(solution: &dyn Solution, parsed: &dyn Parsed) -> Vec<String> {
+    let mut expected = [None, None];
+    let mut failures = Vec::new();
+    for variant in solution.variants() {
+        let index = variant.part as usize - 1;
+        let expected = expected[index].get_or_insert_with(|| match variant.part {
+            1 => parsed.part1().ok(),
+            _ => parsed.part2().and_then(Result::ok),
+        });
+        if let Some(expected) = expected
+            && let Err(e) = variant.agree(parsed.variant(variant), expected)
+        {
+            failures.push(e);
+        }
+    }
+    failures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParseError;
+    use crate::solution::Day;
+
+    struct Sum;
+
+    static VARIANTS: &[Variant<Vec<u32>>] = &[
+        Variant::new("reversed", 1, |numbers| {
+            Ok(numbers.iter().rev().sum::<u32>().to_string())
+        }),
+        Variant::new("first", 1, |numbers| Ok(numbers[0].to_string())),
+        Variant::new("broken", 2, |_| Err("no luck".into())),
+    ];
+
+    impl Day for Sum {
+        type Model = Vec<u32>;
+
+        fn day(&self) -> u32 {
+            1
+        }
+
+        fn title(&self) -> &'static str {
+            "Sum"
+        }
+
+        fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+            input
+                .split(',')
+                .map(|n| n.parse().map_err(ParseError::from))
+                .collect()
+        }
+
+        fn part1(&self, numbers: &Self::Model) -> Answer {
+            Ok(numbers.iter().sum::<u32>().to_string())
+        }
+
+        fn part2(&self, numbers: &Self::Model) -> Option<Answer> {
+            Some(Day::part1(self, numbers))
+        }
+
+        fn variants(&self) -> &'static [Variant<Self::Model>] {
+            VARIANTS
+        }
+    }
+
+    #[test]
+    fn test_disagreements() {
+        let parsed = Solution::parse(&Sum, "1,2,3").unwrap();
+        assert_eq!(
+            disagreements(&Sum, parsed.as_ref()),
+            [
+                "part1_first disagrees: got 1, but part1 got 6",
+                "part2_broken: no luck"
+            ]
+        );
+        let parsed = Solution::parse(&Sum, "6").unwrap();
+        assert_eq!(
+            disagreements(&Sum, parsed.as_ref()),
+            ["part2_broken: no luck"]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "a variant solves part 1 or 2")]
+    fn test_variant_of_no_part() {
+        Variant::<Vec<u32>>::new("none", 0, |_| Ok(String::new()));
+    }
+}
@@ -1,27 +1,46 @@
-use common::read_file_as_string;
+use common::{Answer, Day, ParseError, parse_lines, read_input_or};
 use once_cell::sync::Lazy;
+use std::str::FromStr;
 
-pub static INPUT: Lazy<String> =
-    Lazy::new(|| read_file_as_string("data/day01.txt").expect("Failed to load input"));
-
-fn parse_moves(input: &str) -> Vec<(char, i32)> {
-    input
-        .lines()
-        .map(|l| {
-            let (d, s) = l.split_at(1);
-            (d.chars().next().unwrap(), s.parse::<i32>().unwrap())
-        })
-        .collect()
+/// The example of the puzzle, which stands in for a missing input.
+pub const EXAMPLE: &str = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82";
+
+pub static INPUT: Lazy<Vec<Rotation>> = Lazy::new(|| {
+    Day01
+        .parse(&read_input_or("day01.txt", || EXAMPLE.to_owned()))
+        .expect("Failed to parse input")
+});
+
+#[derive(Clone, Copy, Debug)]
+pub struct Rotation {
+    direction: char,
+    steps: i32,
 }
 
-pub fn part1(input: &str) -> i32 {
+impl FromStr for Rotation {
+    type Err = ParseError;
+
+    // L68
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let direction = match s.chars().next() {
+            Some(direction @ ('L' | 'R')) => direction,
+            _ => return Err(ParseError::new("Invalid direction").at(1, s.get(..1).unwrap_or(s))),
+        };
+        let steps = s[1..]
+            .parse()
+            .map_err(|e| ParseError::from(e).at(2, &s[1..]))?;
+        Ok(Rotation { direction, steps })
+    }
+}
+
+pub fn part1(rotations: &[Rotation]) -> i32 {
     let mut position: i32 = 50; // always in range [0, 100)
     let mut password = 0;
-    for (dir, steps) in parse_moves(input) {
-        match dir {
+    for &Rotation { direction, steps } in rotations {
+        match direction {
             'R' => position += steps % 100,
             'L' => position -= steps % 100,
-            _ => panic!("Invalid direction: {}", dir),
+            _ => panic!("Invalid direction: {}", direction),
         }
         position = position.rem_euclid(100);
         if position == 0 {
@@ -31,15 +50,15 @@ pub fn part1(input: &str) -> i32 {
     password
 }
 
-pub fn part2(input: &str) -> i32 {
+pub fn part2(rotations: &[Rotation]) -> i32 {
     let mut position: i32 = 50; // always in range [0, 100)
     let mut password = 0;
-    for (dir, steps) in parse_moves(input) {
+    for &Rotation { direction, steps } in rotations {
         let old = position;
-        match dir {
+        match direction {
             'R' => position += steps % 100,
             'L' => position -= steps % 100,
-            _ => panic!("Invalid direction: {}", dir),
+            _ => panic!("Invalid direction: {}", direction),
         }
         password += steps / 100;
         if position >= 100 || (old != 0 && position <= 0) {
@@ -50,29 +69,70 @@ pub fn part2(input: &str) -> i32 {
     password
 }
 
+pub struct Day01;
+
+impl Day for Day01 {
+    type Model = Vec<Rotation>;
+
+    fn day(&self) -> u32 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Secret Entrance"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, rotations: &Self::Model) -> Answer {
+        Ok(part1(rotations).to_string())
+    }
+
+    fn part2(&self, rotations: &Self::Model) -> Option<Answer> {
+        Some(Ok(part2(rotations).to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{check_answer, check_input_sets};
 
     #[test]
     fn test_part1_example() {
-        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82".to_string();
-        assert_eq!(part1(&input), 3);
+        let rotations = Day01.parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&rotations), 3);
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&INPUT), 964);
+        check_answer(1, 1, || part1(&INPUT));
     }
 
     #[test]
     fn test_part2_example() {
-        let input = "L68\nL30\nR48\nL5\nR60\nL55\nL1\nL99\nR14\nL82".to_string();
-        assert_eq!(part2(&input), 6);
+        let rotations = Day01.parse(EXAMPLE).unwrap();
+        assert_eq!(part2(&rotations), 6);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&INPUT), 5872);
+        check_answer(1, 2, || part2(&INPUT));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = Day01.parse("L68\nU30").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 1: Invalid direction in \"U\""
+        );
+    }
+
+    #[test]
+    fn test_input_sets() {
+        check_input_sets(&Day01);
     }
 }
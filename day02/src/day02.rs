@@ -1,10 +1,20 @@
 use std::collections::HashSet;
-use common::read_file_as_string;
+use common::intervals::IntervalSet;
+use common::{Answer, Day, ParseError, read_input_or};
 use once_cell::sync::Lazy;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 
-pub static INPUT: Lazy<String> =
-    Lazy::new(|| read_file_as_string("data/day02.txt").expect("Failed to load input"));
+/// The example of the puzzle, which stands in for a missing input.
+pub const EXAMPLE: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
+1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
+824824821-824824827,2121212118-2121212124";
+
+pub static INPUT: Lazy<IntervalSet<u64>> = Lazy::new(|| {
+    Day02
+        .parse(&read_input_or("day02.txt", || EXAMPLE.to_owned()))
+        .expect("Failed to parse input")
+});
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct InvalidIP {
@@ -16,7 +26,7 @@ impl InvalidIP {
         Self { root }
     }
 
-    fn to_ip(&self) -> u64 {
+    fn to_ip(self) -> u64 {
         let root64 = self.root as u64;
         let digits = num_digits_u64(root64);
         let pow = 10u64.pow(digits as u32);
@@ -27,7 +37,7 @@ impl InvalidIP {
         let len = num_digits_u64(ip);
         if len == 1 {
             1
-        } else if len % 2 == 0 {
+        } else if len.is_multiple_of(2) {
             let half = len / 2;
             let pow = 10u64.pow(half as u32);
             let high = (ip / pow) as u32;
@@ -46,7 +56,7 @@ impl InvalidIP {
         let len = num_digits_u64(ip);
         if len == 1 {
             0 // not quite but seems to work for tests
-        } else if len % 2 == 0 {
+        } else if len.is_multiple_of(2) {
             let half = len / 2;
             let pow = 10u64.pow(half as u32);
             let high = (ip / pow) as u32;
@@ -81,21 +91,34 @@ struct Range {
 }
 
 impl FromStr for Range {
-    type Err = String;
+    type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split('-');
-        let min_str = parts.next().ok_or_else(|| "Missing min".to_string())?;
-        let max_str = parts.next().ok_or_else(|| "Missing max".to_string())?;
-        let min = min_str.trim().parse::<u64>().map_err(|e| e.to_string())?;
-        let max = max_str.trim().parse::<u64>().map_err(|e| e.to_string())?;
+        let (min_str, max_str) = s
+            .split_once('-')
+            .ok_or_else(|| ParseError::new("Missing '-'").at(1, s))?;
+        let min = min_str
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| ParseError::from(e).at(1, min_str))?;
+        let max = max_str
+            .trim()
+            .parse::<u64>()
+            .map_err(|e| ParseError::from(e).at(min_str.len() + 2, max_str))?;
         if min <= max {
             Ok(Range { min, max })
         } else {
-            Err(format!("Invalid range: {}-{}", min, max))
+            Err(ParseError::new("Invalid range").at(1, s))
         }
     }
 }
 
+impl From<RangeInclusive<u64>> for Range {
+    fn from(range: RangeInclusive<u64>) -> Self {
+        let (min, max) = range.into_inner();
+        Range { min, max }
+    }
+}
+
 impl Range {
     fn invalids_part1(&self) -> Vec<InvalidIP> {
         let mut result = Vec::new();
@@ -145,31 +168,67 @@ impl Range {
     }
 }
 
-fn parse_ranges(input: &str) -> Vec<Range> {
+// Merged, so that ids in overlapping ranges are only counted once.
+fn parse_ranges(input: &str) -> Result<IntervalSet<u64>, ParseError> {
+    let mut offset = 0;
     input
         .split(',')
-        .map(|l| l.trim().parse().unwrap())
+        .map(|s| {
+            let range = s.trim().parse::<Range>().map_err(|e| {
+                let leading = s.len() - s.trim_start().len();
+                e.shifted(offset + leading).in_line(1, input)
+            });
+            offset += s.len() + 1;
+            range.map(|r| r.min..=r.max)
+        })
         .collect()
 }
 
-pub fn part1(input: &str) -> u64 {
-    parse_ranges(input)
+pub fn part1(ranges: &IntervalSet<u64>) -> u64 {
+    ranges
         .iter()
-        .flat_map(|r| r.invalids_part1())
+        .flat_map(|r| Range::from(r).invalids_part1())
         .map(|i| i.to_ip())
         .sum()
 }
 
-pub fn part2(input: &str) -> u64 {
-    parse_ranges(input)
+pub fn part2(ranges: &IntervalSet<u64>) -> u64 {
+    ranges
         .iter()
-        .flat_map(|r| r.invalids_part2())
+        .flat_map(|r| Range::from(r).invalids_part2())
         .sum()
 }
 
+pub struct Day02;
+
+impl Day for Day02 {
+    type Model = IntervalSet<u64>;
+
+    fn day(&self) -> u32 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Gift Shop"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse_ranges(input)
+    }
+
+    fn part1(&self, ranges: &Self::Model) -> Answer {
+        Ok(part1(ranges).to_string())
+    }
+
+    fn part2(&self, ranges: &Self::Model) -> Option<Answer> {
+        Some(Ok(part2(ranges).to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{check_answer, check_input_sets};
 
     #[test]
     fn test_next_root() {
@@ -195,29 +254,36 @@ mod tests {
         assert_eq!(InvalidIP::previous_root(12345), 99);
     }
 
+    #[test]
+    fn test_parse_error_location() {
+        let e = parse_ranges("11-22, 95-1x5").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (1, 11, "1x5"));
+    }
+
     #[test]
     fn test_example_part1() {
-        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
-                          1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
-                          824824821-824824827,2121212118-2121212124";
-        assert_eq!(part1(input), 1227775554);
+        let ranges = Day02.parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&ranges), 1227775554);
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&INPUT), 30599400849);
+        check_answer(2, 1, || part1(&INPUT));
     }
 
     #[test]
     fn test_example_part2() {
-        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,\
-                          1698522-1698528,446443-446449,38593856-38593862,565653-565659,\
-                          824824821-824824827,2121212118-2121212124";
-        assert_eq!(part2(input), 4174379265);
+        let ranges = Day02.parse(EXAMPLE).unwrap();
+        assert_eq!(part2(&ranges), 4174379265);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&INPUT), 46270373595);
+        check_answer(2, 2, || part2(&INPUT));
+    }
+
+    #[test]
+    fn test_input_sets() {
+        check_input_sets(&Day02);
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,19 @@
-use common::read_file_as_elements;
+use common::{Answer, Day, ParseError, parse_lines, read_input_or};
 use once_cell::sync::Lazy;
 use std::str::FromStr;
 
-pub static INPUT: Lazy<Vec<Bank>> =
-    Lazy::new(|| read_file_as_elements("data/day03.txt").expect("Failed to load input"));
+/// The example of the puzzle, which stands in for a missing input.
+pub const EXAMPLE: &str = "\
+987654321111111
+811111111111119
+234234234234278
+818181911112111";
+
+pub static INPUT: Lazy<Vec<Bank>> = Lazy::new(|| {
+    Day03
+        .parse(&read_input_or("day03.txt", || EXAMPLE.to_owned()))
+        .expect("Failed to parse input")
+});
 
 #[derive(Debug)]
 pub struct Bank {
@@ -65,9 +75,36 @@ pub fn part2(input: &[Bank]) -> u64 {
     input.iter().map(|b| b.maximum_joltage(12)).sum()
 }
 
+pub struct Day03;
+
+impl Day for Day03 {
+    type Model = Vec<Bank>;
+
+    fn day(&self) -> u32 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Lobby"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, banks: &Self::Model) -> Answer {
+        Ok(part1(banks).to_string())
+    }
+
+    fn part2(&self, banks: &Self::Model) -> Option<Answer> {
+        Some(Ok(part2(banks).to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{check_answer, check_input_sets};
     use rstest::rstest;
 
     #[rstest]
@@ -81,7 +118,7 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&INPUT), 17332);
+        check_answer(3, 1, || part1(&INPUT));
     }
 
     #[rstest]
@@ -95,6 +132,11 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&INPUT), 172516781546707);
+        check_answer(3, 2, || part2(&INPUT));
+    }
+
+    #[test]
+    fn test_input_sets() {
+        check_input_sets(&Day03);
     }
 }
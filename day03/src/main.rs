@@ -1,7 +1,6 @@
-mod day03;
-use crate::day03::{INPUT, part1, part2};
+use day03::day03::Day03;
+use std::error::Error;
 
-fn main() {
-    println!("Part1: {}", part1(&INPUT));
-    println!("Part2: {}", part2(&INPUT));
-}
\ No newline at end of file
+fn main() -> Result<(), Box<dyn Error>> {
+    common::solution::run(&Day03)
+}
@@ -1,84 +1,78 @@
-use common::read_file_as_lines;
+use common::grid::Grid;
+use common::{Answer, Day, ParseError, read_input_or};
 use once_cell::sync::Lazy;
 
 const ACCESSIBLE_LIMIT: usize = 4;
-const PAPER: char = '@';
+
+/// The example of the puzzle, which stands in for a missing input.
+pub const EXAMPLE: &str = "\
+..@@.@@@@.
+@@@.@.@.@@
+@@@@@.@.@@
+@.@@@@..@.
+@@.@@@@.@@
+.@@@@@@@.@
+.@.@.@.@@@
+@.@@@.@@@@
+.@@@@@@@@.
+@.@.@@@.@.";
 
 pub static INPUT: Lazy<Diagram> = Lazy::new(|| {
-    let lines = read_file_as_lines("data/day04.txt").expect("Failed to load input");
-    Diagram::from_strings(lines)
+    Day04
+        .parse(&read_input_or("day04.txt", || EXAMPLE.to_owned()))
+        .expect("Failed to parse input")
 });
 
-#[derive(Debug, Clone)]
-pub struct Diagram {
-    // true == '@', false == '.'
-    grid: Vec<Vec<bool>>,
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Paper,
+    Empty,
 }
 
-impl Diagram {
-    pub fn from_strings(lines: Vec<String>) -> Self {
-        Diagram {
-            grid: lines
-                .into_iter()
-                .map(|s| s.chars().map(|c| c == PAPER).collect::<Vec<bool>>())
-                .collect::<Vec<Vec<bool>>>(),
-        }
-    }
-
-    fn height(&self) -> usize {
-        self.grid.len()
-    }
+impl TryFrom<char> for Cell {
+    type Error = char;
 
-    fn width(&self) -> usize {
-        if self.grid.is_empty() {
-            0
-        } else {
-            self.grid[0].len()
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '@' => Ok(Cell::Paper),
+            '.' => Ok(Cell::Empty),
+            _ => Err(c),
         }
     }
+}
 
-    fn is_paper(&self, x: isize, y: isize) -> bool {
-        if y < 0 || x < 0 {
-            false
-        } else if y as usize >= self.height() || x as usize >= self.width() {
-            false
-        } else {
-            self.grid[y as usize][x as usize]
-        }
+#[derive(Debug, Clone)]
+pub struct Diagram {
+    grid: Grid<Cell>,
+}
+
+impl Diagram {
+    pub fn from_lines<S: AsRef<str>>(lines: &[S]) -> Result<Self, ParseError> {
+        Ok(Diagram {
+            grid: Grid::from_lines(lines)?,
+        })
     }
 
     fn count_neighbors(&self, x: usize, y: usize) -> usize {
-        let mut counter = 0;
-        for dx in -1..=1 {
-            for dy in -1..=1 {
-                if dx == 0 && dy == 0 {
-                    continue;
-                }
-                if self.is_paper(x as isize + dx, y as isize + dy) {
-                    counter += 1;
-                }
-            }
-        }
-        counter
+        self.grid
+            .neighbors8(x, y)
+            .filter(|&(_, &cell)| cell == Cell::Paper)
+            .count()
     }
 
     fn accessible_papers(&self) -> Vec<(usize, usize)> {
-        let mut accessible = Vec::new();
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                if self.is_paper(x as isize, y as isize)
-                    && self.count_neighbors(x, y) < ACCESSIBLE_LIMIT
-                {
-                    accessible.push((x, y));
-                }
-            }
-        }
-        accessible
+        self.grid
+            .iter()
+            .filter(|&((x, y), &cell)| {
+                cell == Cell::Paper && self.count_neighbors(x, y) < ACCESSIBLE_LIMIT
+            })
+            .map(|(pos, _)| pos)
+            .collect()
     }
 
     fn remove_accessible_papers(&mut self, to_remove: &[(usize, usize)]) {
-        for (x, y) in to_remove {
-            self.grid[*y][*x] = false;
+        for &pos in to_remove {
+            self.grid[pos] = Cell::Empty;
         }
     }
 }
@@ -100,59 +94,65 @@ pub fn part2(diagram: &Diagram) -> usize {
     }
 }
 
+fn parse_input(input: &str) -> Result<Diagram, ParseError> {
+    Diagram::from_lines(&input.lines().collect::<Vec<_>>())
+}
+
+pub struct Day04;
+
+impl Day for Day04 {
+    type Model = Diagram;
+
+    fn day(&self) -> u32 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Printing Department"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, diagram: &Self::Model) -> Answer {
+        Ok(part1(diagram).to_string())
+    }
+
+    fn part2(&self, diagram: &Self::Model) -> Option<Answer> {
+        Some(Ok(part2(diagram).to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{check_answer, check_input_sets};
 
     #[test]
     fn test_example_part1() {
-        let lines = vec![
-            "..@@.@@@@.",
-            "@@@.@.@.@@",
-            "@@@@@.@.@@",
-            "@.@@@@..@.",
-            "@@.@@@@.@@",
-            ".@@@@@@@.@",
-            ".@.@.@.@@@",
-            "@.@@@.@@@@",
-            ".@@@@@@@@.",
-            "@.@.@@@.@.",
-        ]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        let diagram = Diagram::from_strings(lines);
+        let diagram = Day04.parse(EXAMPLE).unwrap();
         assert_eq!(part1(&diagram), 13);
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&INPUT), 1569);
+        check_answer(4, 1, || part1(&INPUT));
     }
 
     #[test]
     fn test_example_part2() {
-        let lines = vec![
-            "..@@.@@@@.",
-            "@@@.@.@.@@",
-            "@@@@@.@.@@",
-            "@.@@@@..@.",
-            "@@.@@@@.@@",
-            ".@@@@@@@.@",
-            ".@.@.@.@@@",
-            "@.@@@.@@@@",
-            ".@@@@@@@@.",
-            "@.@.@@@.@.",
-        ]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        let diagram = Diagram::from_strings(lines);
+        let diagram = Day04.parse(EXAMPLE).unwrap();
         assert_eq!(part2(&diagram), 43);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&INPUT), 9280);
+        check_answer(4, 2, || part2(&INPUT));
+    }
+
+    #[test]
+    fn test_input_sets() {
+        check_input_sets(&Day04);
     }
 }
@@ -1,9 +1,16 @@
-use day05::day05::{INPUT, part1, part2};
+use common::{Day, read_input_or};
+use day05::day05::{Day05, EXAMPLE, INPUT, part1, part2};
 
 fn main() {
     divan::main()
 }
 
+#[divan::bench]
+fn bench_parse(bencher: divan::Bencher) {
+    let input = read_input_or("day05.txt", || EXAMPLE.to_owned());
+    bencher.bench(|| Day05.parse(divan::black_box(&input)));
+}
+
 #[divan::bench]
 fn bench_part1() {
     part1(divan::black_box(&INPUT));
@@ -12,4 +19,6 @@ fn bench_part1() {
 #[divan::bench]
 fn bench_part2() {
     part2(divan::black_box(&INPUT));
-}
\ No newline at end of file
+}
+
+common::bench_input_sets!(Day05);
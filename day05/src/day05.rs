@@ -1,107 +1,63 @@
-use common::read_file_as_string;
+use common::intervals::IntervalSet;
+use common::parse::{self, Parser, blank_lines, lines, separated_pair, tag, unsigned};
+use common::{Answer, Day, ParseError, read_input_or};
 use once_cell::sync::Lazy;
 use std::str::FromStr;
 
+/// The example of the puzzle, which stands in for a missing input.
+pub const EXAMPLE: &str = "\
+3-5
+10-14
+16-20
+12-18
+
+1
+5
+8
+11
+17
+32";
+
 pub static INPUT: Lazy<DB> = Lazy::new(|| {
-    let input = read_file_as_string("data/day05.txt").expect("Failed to load input");
-    input.parse().expect("Failed to parse input")
+    Day05
+        .parse(&read_input_or("day05.txt", || EXAMPLE.to_owned()))
+        .expect("Failed to parse input")
 });
 
 #[derive(Debug)]
 pub struct DB {
-    // ranges are non-overlapping and sorted
-    ranges: Vec<(u64, u64)>,
-    // available is sorted
+    ranges: IntervalSet<u64>,
     available: Vec<u64>,
 }
 
 impl FromStr for DB {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut empty_line = false;
-        let mut ranges = Vec::new();
-        let mut available = Vec::new();
-        for line in s.lines() {
-            if line.is_empty() {
-                empty_line = true;
-                continue;
-            }
-            if empty_line {
-                available.push(line.parse::<u64>().map_err(|e| e.to_string())?);
-            } else {
-                let split = line.split("-");
-                let begin = split
-                    .clone()
-                    .nth(0)
-                    .unwrap()
-                    .parse::<u64>()
-                    .map_err(|e| e.to_string())?;
-                let end = split
-                    .clone()
-                    .nth(1)
-                    .unwrap()
-                    .parse::<u64>()
-                    .map_err(|e| e.to_string())?;
-                ranges.push((begin, end));
-            }
-        }
-        available.sort_unstable();
+        // fresh ranges (3-5), a blank line and the available ids
+        let range = separated_pair(unsigned(), tag("-"), unsigned()).map(|(b, e)| b..=e);
+        let db = separated_pair(lines(range), blank_lines(), lines(unsigned()));
+        let (ranges, available) = parse::complete(s, db)?;
         Ok(DB {
-            ranges: non_overlapping_and_sorted(ranges),
+            ranges: ranges.into_iter().collect(),
             available,
         })
     }
 }
 
-fn non_overlapping_and_sorted(ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
-    let mut sorted_ranges = ranges.clone();
-    sorted_ranges.sort_unstable();
-    let mut current = sorted_ranges[0];
-    let mut non_averlapping_ranges = Vec::new();
-    for next in sorted_ranges.iter().skip(1) {
-        // current.0 <= next.0 because sorted
-        if next.0 <= current.1 {
-            // current overlaps with range
-            current.1 = u64::max(current.1, next.1);
-        } else {
-            non_averlapping_ranges.push(current);
-            current = *next;
-        }
-    }
-    non_averlapping_ranges.push(current);
-    non_averlapping_ranges
-}
-
 impl DB {
     fn fresh_ingredients(&self) -> usize {
-        let mut i = 0;
-        let mut j = 0;
-        let mut fresh = 0;
-        while i < self.available.len() && j < self.ranges.len() {
-            let at = self.available[i];
-            let (begin, end) = self.ranges[j];
-            if at < begin {
-                // before, so we can discard at
-                i += 1;
-            } else if end < at {
-                // after, we can discard the range
-                j += 1;
-            } else {
-                // inside, we count it
-                fresh += 1;
-                i += 1;
-            }
-        }
-        fresh
+        self.available
+            .iter()
+            .filter(|&&id| self.ranges.contains(id))
+            .count()
     }
 
     fn total_fresh(&self) -> u64 {
-        self.ranges.iter().map(|(begin, end)| end - begin + 1).sum()
+        u64::try_from(self.ranges.len()).expect("Too many fresh ingredients")
     }
 }
 
-// 548 too low
 pub fn part1(input: &DB) -> usize {
     input.fresh_ingredients()
 }
@@ -110,49 +66,65 @@ pub fn part2(input: &DB) -> u64 {
     input.total_fresh()
 }
 
+pub struct Day05;
+
+impl Day for Day05 {
+    type Model = DB;
+
+    fn day(&self) -> u32 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Cafeteria"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        input.parse()
+    }
+
+    fn part1(&self, db: &Self::Model) -> Answer {
+        Ok(part1(db).to_string())
+    }
+
+    fn part2(&self, db: &Self::Model) -> Option<Answer> {
+        Some(Ok(part2(db).to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{check_answer, check_input_sets};
 
     #[test]
-    fn test_exaple_part1() {
-        let input = "3-5
-10-14
-16-20
-12-18
+    fn test_example_part1() {
+        assert_eq!(part1(&Day05.parse(EXAMPLE).unwrap()), 3);
+    }
 
-1
-5
-8
-11
-17
-32";
-        assert_eq!(part1(&DB::from_str(input).unwrap()), 3);
+    #[test]
+    fn test_parse_error_location() {
+        let e = DB::from_str("3-5\n10-1x\n\n1").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 5, "x"));
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&INPUT), 563);
+        check_answer(5, 1, || part1(&INPUT));
     }
 
     #[test]
-    fn test_exaple_part2() {
-        let input = "3-5
-10-14
-16-20
-12-18
-
-1
-5
-8
-11
-17
-32";
-        assert_eq!(part2(&DB::from_str(input).unwrap()), 14);
+    fn test_example_part2() {
+        assert_eq!(part2(&Day05.parse(EXAMPLE).unwrap()), 14);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&INPUT), 338693411431456);
+        check_answer(5, 2, || part2(&INPUT));
+    }
+
+    #[test]
+    fn test_input_sets() {
+        check_input_sets(&Day05);
     }
 }
@@ -1,7 +1,6 @@
-mod day05;
-use crate::day05::{INPUT, part1, part2};
+use day05::day05::Day05;
+use std::error::Error;
 
-fn main() {
-    println!("Part1: {}", part1(&INPUT));
-    println!("Part2: {}", part2(&INPUT));
-}
\ No newline at end of file
+fn main() -> Result<(), Box<dyn Error>> {
+    common::solution::run(&Day05)
+}
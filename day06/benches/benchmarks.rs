@@ -1,9 +1,16 @@
-use day06::day06::{INPUT, part1, part2, part1_iterative, part2_iterative, part2_strings};
+use common::{Day, Input, Variant, read_input_or};
+use day06::day06::{Day06, EXAMPLE, INPUT, Worksheet, part1, part2};
 
 fn main() {
     divan::main()
 }
 
+#[divan::bench]
+fn bench_parse(bencher: divan::Bencher) {
+    let input = read_input_or("day06.txt", || EXAMPLE.to_owned());
+    bencher.bench(|| Day06.parse(divan::black_box(&input)));
+}
+
 #[divan::bench]
 fn bench_part1() {
     part1(divan::black_box(&INPUT));
@@ -14,17 +21,24 @@ fn bench_part2() {
     part2(divan::black_box(&INPUT));
 }
 
+// Loading the input as owned lines, as the days did before common::Input,
+// against loading it as an Input: the one baseline kept in the benches.
 #[divan::bench]
-fn bench_part1_iterative() {
-    part1_iterative(divan::black_box(&INPUT));
+fn bench_load_lines() -> Vec<String> {
+    read_input_or("day06.txt", || EXAMPLE.to_owned())
+        .lines()
+        .map(str::to_owned)
+        .collect()
 }
 
 #[divan::bench]
-fn bench_part2_iterative() {
-    part2_iterative(divan::black_box(&INPUT));
+fn bench_load_input() -> Input {
+    Input::new(read_input_or("day06.txt", || EXAMPLE.to_owned()))
 }
 
-#[divan::bench]
-fn bench_part2_strings() {
-    part2_strings(divan::black_box(&INPUT));
-}
\ No newline at end of file
+#[divan::bench(args = Day06.variants())]
+fn bench_variants(bencher: divan::Bencher, variant: &Variant<Worksheet>) {
+    bencher.bench(|| variant.solve(divan::black_box(&INPUT)));
+}
+
+common::bench_input_sets!(Day06);
@@ -1,47 +1,164 @@
-use common::read_file_as_lines;
-use itertools::Itertools;
+use common::grid::Grid;
+use common::{Answer, Day, Input, ParseError, Variant, read_input_or};
 use once_cell::sync::Lazy;
-use std::str::FromStr;
+use std::num::ParseIntError;
+use std::ops::Range;
 
-pub static INPUT: Lazy<Vec<String>> =
-    Lazy::new(|| read_file_as_lines("data/day06.txt").expect("Failed to load input"));
+/// The example of the puzzle, which stands in for a missing input.
+pub const EXAMPLE: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
 
+pub static INPUT: Lazy<Worksheet> = Lazy::new(|| {
+    Day06
+        .parse(&read_input_or("day06.txt", || EXAMPLE.to_owned()))
+        .expect("Failed to parse input")
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Operation {
     Sum,
     Mult,
 }
 
-impl FromStr for Operation {
-    type Err = String;
+impl Operation {
+    fn apply(self, args: impl Iterator<Item = u64>) -> u64 {
+        match self {
+            Operation::Sum => args.sum(),
+            Operation::Mult => args.product(),
+        }
+    }
+}
+
+impl TryFrom<char> for Operation {
+    type Error = char;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s == "+" {
-            Ok(Operation::Sum)
-        } else if s == "*" {
-            Ok(Operation::Mult)
-        } else {
-            Err(format!("Unknown operation {}", s))
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '+' => Ok(Operation::Sum),
+            '*' => Ok(Operation::Mult),
+            _ => Err(c),
         }
     }
 }
 
-pub fn part1_iterative(input: &[String]) -> u64 {
-    let numbers = input[..input.len() - 1]
-        .iter()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|x| x.parse::<u64>().unwrap())
-                .collect()
+/// A cell of the rows of numbers of the worksheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Blank,
+    Digit(u8),
+}
+
+impl TryFrom<char> for Cell {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            ' ' => Ok(Cell::Blank),
+            _ => c.to_digit(10).map(|d| Cell::Digit(d as u8)).ok_or(c),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Problem {
+    operation: Operation,
+    /// Columns of the worksheet it is written in.
+    columns: Range<usize>,
+}
+
+/// The rows of numbers, digit by digit, and the problems written in them,
+/// which part 1 reads in rows and part 2 in columns.
+#[derive(Debug, Clone)]
+pub struct Worksheet {
+    digits: Grid<Cell>,
+    problems: Vec<Problem>,
+}
+
+impl Worksheet {
+    fn rows<'a>(&'a self, problem: &'a Problem) -> impl Iterator<Item = &'a [Cell]> {
+        self.digits.rows().map(|row| &row[problem.columns.clone()])
+    }
+}
+
+fn number<'a>(cells: impl IntoIterator<Item = &'a Cell>) -> u64 {
+    cells.into_iter().fold(0, |acc, cell| match cell {
+        Cell::Digit(d) => acc * 10 + *d as u64,
+        Cell::Blank => acc,
+    })
+}
+
+// the rows of numbers, then the operations, each one under the first column
+// of its problem; problems are separated by columns of blanks
+fn parse_worksheet(input: &str) -> Result<Worksheet, ParseError> {
+    let sheet = Input::from(input);
+    let Some(height) = sheet.len().checked_sub(1) else {
+        return Err(ParseError::new("Missing operations"));
+    };
+    let digits = Grid::<Cell>::from_lines(sheet.lines().take(height))?;
+    let mut spans = Vec::new();
+    let mut start = 0;
+    for x in 0..=digits.width() {
+        if x == digits.width() || sheet.column(x, 0..height).all(|b| b == b' ') {
+            if x > start {
+                spans.push(start..x);
+            }
+            start = x + 1;
+        }
+    }
+    let last = sheet.line(height);
+    let line = height + 1;
+    let operations = last
+        .chars()
+        .enumerate()
+        .filter(|&(_, c)| c != ' ')
+        .map(|(x, c)| {
+            Operation::try_from(c).map_err(|c| {
+                ParseError::new("Invalid operation")
+                    .at(x + 1, &c.to_string())
+                    .in_line(line, last)
+            })
         })
-        .collect::<Vec<Vec<_>>>();
-    let ops = input[input.len() - 1]
-        .split_whitespace()
-        .map(|x| x.parse::<Operation>().unwrap())
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, _>>()?;
+    if operations.len() != spans.len() {
+        return Err(
+            ParseError::new(format!("Expected {} operations", spans.len())).in_line(line, last),
+        );
+    }
+    for (y, row) in digits.rows().enumerate() {
+        for columns in &spans {
+            // the digits of a number are together, with blanks around them
+            let cells = &row[columns.clone()];
+            let digits = cells
+                .iter()
+                .position(|&c| c != Cell::Blank)
+                .zip(cells.iter().rposition(|&c| c != Cell::Blank));
+            let message = match digits {
+                None => "Missing number",
+                Some((first, last)) if cells[first..last].contains(&Cell::Blank) => {
+                    "Expected a single number"
+                }
+                Some(_) => continue,
+            };
+            return Err(ParseError::new(message)
+                .at(columns.start + 1, &sheet.line(y)[columns.clone()])
+                .in_line(y + 1, sheet.line(y)));
+        }
+    }
+    let problems = spans
+        .into_iter()
+        .zip(operations)
+        .map(|(columns, operation)| Problem { operation, columns })
+        .collect();
+    Ok(Worksheet { digits, problems })
+}
+
+pub fn part1_iterative(worksheet: &Worksheet) -> u64 {
     let mut total = 0;
-    for i in 0..ops.len() {
-        let args = numbers.iter().map(|row| row[i]).collect::<Vec<_>>();
-        let result = match ops[i] {
+    for problem in &worksheet.problems {
+        let mut args = Vec::new();
+        for row in worksheet.rows(problem) {
+            args.push(number(row));
+        }
+        let result = match problem.operation {
             Operation::Sum => args.iter().sum::<u64>(),
             Operation::Mult => args.iter().product::<u64>(),
         };
@@ -50,36 +167,21 @@ pub fn part1_iterative(input: &[String]) -> u64 {
     total
 }
 
-pub fn part2_iterative(input: &[String]) -> u64 {
-    let numbers = &input[..input.len() - 1];
-    assert!(numbers.iter().all(|row| row.len() == numbers[0].len()));
-    let len = numbers[0].len();
-    let transposed_numbers = (0..len)
-        .map(|i| {
-            numbers
-                .iter()
-                .map(|row| row.chars().nth(i).unwrap())
-                .collect::<String>()
-                .trim()
-                .to_string()
-        })
-        .collect::<Vec<_>>();
+pub fn part2_iterative(worksheet: &Worksheet) -> u64 {
+    let digits = &worksheet.digits;
     let mut total = 0;
-    let mut it = transposed_numbers.iter();
     let mut args = Vec::new();
-    for op in input[input.len() - 1]
-        .split_whitespace()
-        .map(|x| x.parse::<Operation>().unwrap())
-    {
-        loop {
-            let empty = "".to_string();
-            let s = it.next().unwrap_or(&empty);
-            if s.is_empty() {
-                break;
+    for problem in &worksheet.problems {
+        for x in problem.columns.clone() {
+            let mut n = 0;
+            for y in 0..digits.height() {
+                if let Cell::Digit(d) = digits[(x, y)] {
+                    n = n * 10 + d as u64;
+                }
             }
-            args.push(s.parse::<u64>().unwrap());
+            args.push(n);
         }
-        total += match op {
+        total += match problem.operation {
             Operation::Sum => args.iter().sum::<u64>(),
             Operation::Mult => args.iter().product::<u64>(),
         };
@@ -88,143 +190,150 @@ pub fn part2_iterative(input: &[String]) -> u64 {
     total
 }
 
-pub fn part2_strings(input: &[String]) -> u64 {
-    let transposed_numbers = (0..input[0].len())
-        .map(|i| {
-            input
-                .iter()
-                .take(input.len() - 1)
-                .map(|row| row.chars().nth(i).unwrap())
-                .collect::<String>()
-        })
-        .map(|num_str| num_str.trim().parse::<u64>().unwrap_or(0))
-        .chunk_by(|n| *n != 0); // no column has value zero, so we use it to mark separators
-    let ops = input[input.len() - 1]
-        .split_whitespace()
-        .map(|x| x.parse::<Operation>().unwrap());
-    transposed_numbers
-        .into_iter()
-        .filter_map(|(has_numbers, numbers)| has_numbers.then_some(numbers))
-        .zip(ops)
-        .map(|(numbers, op)| match op {
-            Operation::Sum => numbers.sum::<u64>(),
-            Operation::Mult => numbers.product::<u64>(),
-        })
-        .sum::<u64>()
+pub fn part2_strings(worksheet: &Worksheet) -> Result<u64, ParseIntError> {
+    let mut total = 0;
+    for problem in &worksheet.problems {
+        let args = problem
+            .columns
+            .clone()
+            .map(|x| {
+                worksheet
+                    .digits
+                    .column(x)
+                    .filter_map(|&c| match c {
+                        Cell::Digit(d) => Some(char::from(b'0' + d)),
+                        Cell::Blank => None,
+                    })
+                    .collect::<String>()
+                    .parse::<u64>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        total += problem.operation.apply(args.into_iter());
+    }
+    Ok(total)
 }
 
-pub fn part1(input: &[String]) -> u64 {
-    let mut numbers = input[..input.len() - 1]
+pub fn part1(worksheet: &Worksheet) -> u64 {
+    worksheet
+        .problems
         .iter()
-        .map(|line| line.split_whitespace().map(|x| x.parse::<u64>().unwrap()))
-        .collect::<Vec<_>>();
-    let ops = input[input.len() - 1]
-        .split_whitespace()
-        .map(|x| x.parse::<Operation>().unwrap());
-    ops.map(|op| {
-        let args = numbers.iter_mut().map(|row| row.next().unwrap());
-        match op {
-            Operation::Sum => args.sum::<u64>(),
-            Operation::Mult => args.product::<u64>(),
-        }
-    })
-    .sum::<u64>()
+        .map(|problem| problem.operation.apply(worksheet.rows(problem).map(number)))
+        .sum()
 }
 
-pub fn part2(input: &[String]) -> u64 {
-    let transposed_numbers = (0..input[0].len())
-        .map(|i| {
-            input
-                .iter()
-                .take(input.len() - 1)
-                .map(|row| row.chars().nth(i).unwrap())
-                .fold(0, |acc, c| {
-                    if c.is_digit(10) {
-                        acc * 10 + (c as u64 - '0' as u64)
-                    } else {
-                        acc
-                    }
-                })
-        })
-        .chunk_by(|n| *n != 0); // no column has value zero, so we use it to mark separators
-    let ops = input[input.len() - 1]
-        .split_whitespace()
-        .map(|x| x.parse::<Operation>().unwrap());
-    transposed_numbers
-        .into_iter()
-        .filter_map(|(has_numbers, numbers)| has_numbers.then_some(numbers))
-        .zip(ops)
-        .map(|(numbers, op)| match op {
-            Operation::Sum => numbers.sum::<u64>(),
-            Operation::Mult => numbers.product::<u64>(),
+pub fn part2(worksheet: &Worksheet) -> u64 {
+    worksheet
+        .problems
+        .iter()
+        .map(|problem| {
+            let numbers = problem
+                .columns
+                .clone()
+                .map(|x| number(worksheet.digits.column(x)));
+            problem.operation.apply(numbers)
         })
-        .sum::<u64>()
+        .sum()
+}
+
+static VARIANTS: &[Variant<Worksheet>] = &[
+    Variant::new("iterative", 1, |worksheet| {
+        Ok(part1_iterative(worksheet).to_string())
+    }),
+    Variant::new("iterative", 2, |worksheet| {
+        Ok(part2_iterative(worksheet).to_string())
+    }),
+    Variant::new("strings", 2, |worksheet| {
+        Ok(part2_strings(worksheet)?.to_string())
+    }),
+];
+
+pub struct Day06;
+
+impl Day for Day06 {
+    type Model = Worksheet;
+
+    fn day(&self) -> u32 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Trash Compactor"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse_worksheet(input)
+    }
+
+    fn part1(&self, worksheet: &Self::Model) -> Answer {
+        Ok(part1(worksheet).to_string())
+    }
+
+    fn part2(&self, worksheet: &Self::Model) -> Option<Answer> {
+        Some(Ok(part2(worksheet).to_string()))
+    }
+
+    fn variants(&self) -> &'static [Variant<Self::Model>] {
+        VARIANTS
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{check_answer, check_input_sets, check_variants};
 
     #[test]
     fn test_example_part1() {
-        let input = "123 328  51 64
- 45 64  387 23
-  6 98  215 314
-*   +   *   + ";
-        let input = input.lines().map(|x| x.to_string()).collect::<Vec<_>>();
-        assert_eq!(part1(&input), 4277556);
-    }
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(part1(&INPUT), 4405895212738);
+        let worksheet = Day06.parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&worksheet), 4277556);
     }
 
     #[test]
-    fn test_example_part2() {
-        let input = vec![
-            "123 328  51 64 ",
-            " 45 64  387 23 ",
-            "  6 98  215 314",
-            "*   +   *   +  ",
-        ]
-        .iter()
-        .map(|x| x.to_string())
-        .collect::<Vec<_>>();
-        assert_eq!(part2(&input), 3263827);
+    fn test_parse_errors() {
+        let e = parse_worksheet("12 3\n4x 5\n+  *").unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 2: Invalid cell 'x' in \"x\"");
+        let e = parse_worksheet("12 3\n45 6\n+  -").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 3, column 4: Invalid operation in \"-\""
+        );
+        let e = parse_worksheet("12 3\n45 6\n+").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 3, column 1: Expected 2 operations in \"+\""
+        );
+        let e = parse_worksheet("12 3 4\n45   6\n+  *  *").unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 4: Missing number in \" \"");
+        let e = parse_worksheet("1 2 3\n456 6\n+   *").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 1: Expected a single number in \"1 2\""
+        );
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(part2(&INPUT), 7450962489289);
+    fn test_part1() {
+        check_answer(6, 1, || part1(&INPUT));
     }
 
     #[test]
-    fn test_example_part2_iterative() {
-        let input = vec![
-            "123 328  51 64 ",
-            " 45 64  387 23 ",
-            "  6 98  215 314",
-            "*   +   *   +  ",
-        ]
-        .iter()
-        .map(|x| x.to_string())
-        .collect::<Vec<_>>();
-        assert_eq!(part2_iterative(&input), 3263827);
+    fn test_example_part2() {
+        let worksheet = Day06.parse(EXAMPLE).unwrap();
+        assert_eq!(part2(&worksheet), 3263827);
     }
 
     #[test]
-    fn test_part1_iterative() {
-        assert_eq!(part1_iterative(&INPUT), 4405895212738);
+    fn test_part2() {
+        check_answer(6, 2, || part2(&INPUT));
     }
 
     #[test]
-    fn test_part2_iterative() {
-        assert_eq!(part2_iterative(&INPUT), 7450962489289);
+    fn test_variants() {
+        check_variants(&Day06, EXAMPLE);
     }
 
     #[test]
-    fn test_part2_strings() {
-        assert_eq!(part2_strings(&INPUT), 7450962489289);
+    fn test_input_sets() {
+        check_input_sets(&Day06);
     }
 }
@@ -1,7 +1,6 @@
-mod day06;
-use crate::day06::{INPUT, part1, part2};
+use day06::day06::Day06;
+use std::error::Error;
 
-fn main() {
-    println!("Part1: {}", part1(&INPUT));
-    println!("Part2: {}", part2(&INPUT));
-}
\ No newline at end of file
+fn main() -> Result<(), Box<dyn Error>> {
+    common::solution::run(&Day06)
+}
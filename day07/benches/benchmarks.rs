@@ -1,3 +1,4 @@
+use common::{Day, Variant, read_input_or};
 use day07::day07::*;
 
 fn main() {
@@ -5,37 +6,24 @@ fn main() {
 }
 
 #[divan::bench]
-fn bench_part1_chars_bitset() {
-    part1_chars_bitset(divan::black_box(&INPUT));
+fn bench_parse(bencher: divan::Bencher) {
+    let input = read_input_or("day07.txt", || EXAMPLE.to_owned());
+    bencher.bench(|| Day07.parse(divan::black_box(&input)));
 }
 
-#[divan::bench]
-fn bench_part1_bytes_bitset() {
-    part1_bytes_bitset(divan::black_box(&INPUT));
-}
-
-#[divan::bench]
-fn bench_part1_chars_array() {
-    part1_chars_array(divan::black_box(&INPUT));
-}
-
-#[divan::bench]
-fn bench_part1_slower() {
-    part1_slower(divan::black_box(&INPUT));
-}
-
-
 #[divan::bench]
 fn bench_part1() {
     part1(divan::black_box(&INPUT));
 }
 
-#[divan::bench]
-fn bench_part2_chars_array() {
-    part2_chars_array(divan::black_box(&INPUT));
-}
-
 #[divan::bench]
 fn bench_part2() {
     part2(divan::black_box(&INPUT));
-}
\ No newline at end of file
+}
+
+#[divan::bench(args = Day07.variants())]
+fn bench_variants(bencher: divan::Bencher, variant: &Variant<Manifold>) {
+    bencher.bench(|| variant.solve(divan::black_box(&INPUT)));
+}
+
+common::bench_input_sets!(Day07);
@@ -1,49 +1,75 @@
 use bit_set::BitSet;
-use common::read_file_as_lines;
+use common::grid::Grid;
+use common::{Answer, Day, Input, ParseError, Variant, read_input_or};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 
-pub static INPUT: Lazy<Vec<String>> =
-    Lazy::new(|| read_file_as_lines("data/day07.txt").expect("Failed to load input"));
+/// The example of the puzzle, which stands in for a missing input.
+pub const EXAMPLE: &str = "\
+.......S.......
+...............
+.......^.......
+...............
+......^.^......
+...............
+.....^.^.^.....
+...............
+....^.^...^....
+...............
+...^.^...^.^...
+...............
+..^...^.....^..
+...............
+.^.^.^.^.^...^.
+...............";
 
-#[allow(dead_code)]
-pub fn part1_chars_bitset(input: &[String]) -> usize {
-    let first_ray = &input[0].find("S").expect("Missing first ray");
-    let mut rays = BitSet::with_capacity(input[0].len());
-    rays.insert(*first_ray);
-    let mut total_splits = 0;
-    for line in input.iter().skip(1) {
-        let splitters = line
-            .chars()
-            .enumerate()
-            .filter_map(|(i, c)| (c == '^').then_some(i))
-            .collect::<BitSet<_>>();
-        if splitters.is_empty() {
-            continue;
+pub static INPUT: Lazy<Manifold> = Lazy::new(|| {
+    Day07
+        .parse(&read_input_or("day07.txt", || EXAMPLE.to_owned()))
+        .expect("Failed to parse input")
+});
+
+/// The diagram of the manifold and the column of the first ray, which
+/// enters it at the top.
+#[derive(Debug, Clone)]
+pub struct Manifold {
+    pub grid: Grid<u8>,
+    pub first_ray: usize,
+}
+
+fn parse_manifold(input: &str) -> Result<Manifold, ParseError> {
+    let lines = Input::from(input);
+    let grid = Grid::<u8>::from_lines(lines.lines())?;
+    for ((x, y), &b) in grid.iter() {
+        if !matches!((b, y), (b'.' | b'^', _) | (b'S', 0)) {
+            return Err(ParseError::new(format!("Invalid cell {:?}", char::from(b)))
+                .at(x + 1, &char::from(b).to_string())
+                .in_line(y + 1, lines.line(y)));
+        }
+        // its rays would leave the manifold
+        if b == b'^' && (x == 0 || x + 1 == grid.width()) {
+            return Err(ParseError::new("Splitter at the edge")
+                .at(x + 1, "^")
+                .in_line(y + 1, lines.line(y)));
         }
-        let split_rays = rays.intersection(&splitters).collect::<BitSet<_>>();
-        total_splits += split_rays.len();
-        let new_rays = split_rays
-            .iter()
-            .flat_map(|ray| [ray - 1, ray + 1])
-            .collect::<BitSet<usize>>();
-        let old_rays = rays.difference(&split_rays).collect::<BitSet<_>>();
-        rays = new_rays.union(&old_rays).collect::<BitSet<_>>();
     }
-    total_splits
+    let first_ray = grid
+        .rows()
+        .next()
+        .and_then(|row| row.iter().position(|&b| b == b'S'))
+        .ok_or_else(|| ParseError::new("Missing first ray"))?;
+    Ok(Manifold { grid, first_ray })
 }
 
-#[allow(dead_code)]
-pub fn part1_bytes_bitset(input: &[String]) -> usize {
-    let first_ray = &input[0].find("S").expect("Missing first ray");
-    let mut rays = BitSet::with_capacity(input[0].len());
-    rays.insert(*first_ray);
+pub fn part1_bytes_bitset(manifold: &Manifold) -> usize {
+    let mut rays = BitSet::with_capacity(manifold.grid.width());
+    rays.insert(manifold.first_ray);
     let mut total_splits = 0;
-    for line in input.iter().skip(1) {
-        let splitters = line
-            .bytes()
+    for row in manifold.grid.rows().skip(1) {
+        let splitters = row
+            .iter()
             .enumerate()
-            .filter_map(|(i, c)| (c == b'^').then_some(i))
+            .filter_map(|(i, &b)| (b == b'^').then_some(i))
             .collect::<BitSet<_>>();
         if splitters.is_empty() {
             continue;
@@ -60,38 +86,14 @@ pub fn part1_bytes_bitset(input: &[String]) -> usize {
     total_splits
 }
 
-#[allow(dead_code)]
-pub fn part1_chars_array(input: &[String]) -> usize {
-    let first_ray = input[0].find("S").expect("Missing first ray");
-    let mut timelines = vec![false; input[0].len()];
-    timelines[first_ray] = true;
+pub fn part1_slower(manifold: &Manifold) -> usize {
+    let mut rays = BitSet::with_capacity(manifold.grid.width());
+    rays.insert(manifold.first_ray);
     let mut total_splits = 0;
-    for line in input.iter().skip(1) {
-        for i in 0..timelines.len() {
-            if !timelines[i] {
-                continue;
-            }
-            if line.chars().nth(i) == Some('^') {
-                timelines[i - 1] = true;
-                timelines[i + 1] = true;
-                timelines[i] = false;
-                total_splits += 1;
-            }
-        }
-    }
-    total_splits
-}
-
-#[allow(dead_code)]
-pub fn part1_slower(input: &[String]) -> usize {
-    let first_ray = &input[0].find("S").expect("Missing first ray");
-    let mut rays = BitSet::with_capacity(input[0].len());
-    rays.insert(*first_ray);
-    let mut total_splits = 0;
-    for line in input.iter().skip(1) {
+    for row in manifold.grid.rows().skip(1) {
         let snapshot = rays.clone();
         for ray in snapshot.iter() {
-            if line.chars().nth(ray) == Some('^') {
+            if row[ray] == b'^' {
                 rays.remove(ray);
                 rays.insert(ray - 1);
                 rays.insert(ray + 1);
@@ -102,17 +104,15 @@ pub fn part1_slower(input: &[String]) -> usize {
     total_splits
 }
 
-#[allow(dead_code)]
-pub fn part2_slower(input: &[String]) -> usize {
-    let first_ray = input[0].find("S").expect("Missing first ray");
+pub fn part2_slower(manifold: &Manifold) -> usize {
     let mut timelines = HashMap::new();
-    timelines.insert(first_ray, 1);
+    timelines.insert(manifold.first_ray, 1);
 
-    for line in input.iter().skip(1) {
+    for row in manifold.grid.rows().skip(1) {
         let mut next: HashMap<usize, usize> = HashMap::new();
 
         for (&ray, &count) in timelines.iter() {
-            if line.chars().nth(ray) == Some('.') {
+            if row[ray] == b'.' {
                 *next.entry(ray).or_insert(0) += count;
             } else {
                 *next.entry(ray - 1).or_insert(0) += count;
@@ -124,34 +124,11 @@ pub fn part2_slower(input: &[String]) -> usize {
     timelines.values().sum()
 }
 
-#[allow(dead_code)]
-pub fn part2_chars_array(input: &[String]) -> usize {
-    let first_ray = input[0].find("S").expect("Missing first ray");
-    let mut timelines = vec![0; input[0].len()];
-    timelines[first_ray] = 1;
-
-    for line in input.iter().skip(1) {
-        for i in 0..timelines.len() {
-            if timelines[i] == 0 {
-                continue;
-            }
-            if line.chars().nth(i) == Some('^') {
-                timelines[i - 1] += timelines[i];
-                timelines[i + 1] += timelines[i];
-                timelines[i] = 0;
-            }
-        }
-    }
-    timelines.iter().sum()
-}
-
-pub fn part1(input: &[String]) -> usize {
-    let first_ray = input[0].find("S").expect("Missing first ray");
-    let mut timelines = vec![false; input[0].len()];
-    timelines[first_ray] = true;
+pub fn part1(manifold: &Manifold) -> usize {
+    let mut timelines = vec![false; manifold.grid.width()];
+    timelines[manifold.first_ray] = true;
     let mut total_splits = 0;
-    for line in input.iter().skip(1) {
-        let bytes = line.as_bytes();
+    for bytes in manifold.grid.rows().skip(1) {
         for i in 0..timelines.len() {
             if !timelines[i] {
                 continue;
@@ -167,13 +144,11 @@ pub fn part1(input: &[String]) -> usize {
     total_splits
 }
 
-pub fn part2(input: &[String]) -> usize {
-    let first_ray = input[0].find("S").expect("Missing first ray");
-    let mut timelines = vec![0; input[0].len()];
-    timelines[first_ray] = 1;
+pub fn part2(manifold: &Manifold) -> usize {
+    let mut timelines = vec![0; manifold.grid.width()];
+    timelines[manifold.first_ray] = 1;
 
-    for line in input.iter().skip(1) {
-        let bytes = line.as_bytes();
+    for bytes in manifold.grid.rows().skip(1) {
         for i in 0..timelines.len() {
             if timelines[i] == 0 {
                 continue;
@@ -188,97 +163,95 @@ pub fn part2(input: &[String]) -> usize {
     timelines.iter().sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+static VARIANTS: &[Variant<Manifold>] = &[
+    Variant::new("bytes_bitset", 1, |manifold| {
+        Ok(part1_bytes_bitset(manifold).to_string())
+    }),
+    Variant::new("slower", 1, |manifold| {
+        Ok(part1_slower(manifold).to_string())
+    }),
+    Variant::new("slower", 2, |manifold| {
+        Ok(part2_slower(manifold).to_string())
+    }),
+];
 
-    #[test]
-    fn test_example_part1() {
-        let input = "\
-.......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-..............."
-            .lines()
-            .map(|l| l.to_string())
-            .collect::<Vec<_>>();
-        assert_eq!(part1_chars_bitset(&input), 21);
+pub struct Day07;
+
+impl Day for Day07 {
+    type Model = Manifold;
+
+    fn day(&self) -> u32 {
+        7
     }
 
-    #[test]
-    fn test_part1_chars_bitset() {
-        assert_eq!(part1_chars_bitset(&INPUT), 1703);
+    fn title(&self) -> &'static str {
+        "Laboratories"
     }
 
-    #[test]
-    fn test_part1_bytes_bitset() {
-        assert_eq!(part1_bytes_bitset(&INPUT), 1703);
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse_manifold(input)
     }
 
+    fn part1(&self, manifold: &Self::Model) -> Answer {
+        Ok(part1(manifold).to_string())
+    }
+
+    fn part2(&self, manifold: &Self::Model) -> Option<Answer> {
+        Some(Ok(part2(manifold).to_string()))
+    }
+
+    fn variants(&self) -> &'static [Variant<Self::Model>] {
+        VARIANTS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{check_answer, check_input_sets, check_variants};
+
     #[test]
-    fn test_part1_chars_array() {
-        assert_eq!(part1_chars_array(&INPUT), 1703);
+    fn test_example_part1() {
+        let manifold = Day07.parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&manifold), 21);
     }
 
     #[test]
-    fn test_part1_slower() {
-        assert_eq!(part1_slower(&INPUT), 1703);
+    fn test_parse_errors() {
+        let e = parse_manifold("..S..\n.^.x.").unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 4: Invalid cell 'x' in \"x\"");
+        let e = parse_manifold(".....\n..^..").unwrap_err();
+        assert_eq!(e.message, "Missing first ray");
+        let e = parse_manifold("..S..\n....^").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 5: Splitter at the edge in \"^\""
+        );
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&INPUT), 1703);
+        check_answer(7, 1, || part1(&INPUT));
     }
 
     #[test]
     fn test_example_part2() {
-        let input = "\
-.......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-..............."
-            .lines()
-            .map(|l| l.to_string())
-            .collect::<Vec<_>>();
-        assert_eq!(part2(&input), 40);
+        let manifold = Day07.parse(EXAMPLE).unwrap();
+        assert_eq!(part2(&manifold), 40);
     }
 
     #[test]
-    fn test_part2_slower() {
-        assert_eq!(part2_slower(&INPUT), 171692855075500);
+    fn test_part2() {
+        check_answer(7, 2, || part2(&INPUT));
     }
 
     #[test]
-    fn test_part2_chars() {
-        assert_eq!(part2_chars_array(&INPUT), 171692855075500);
+    fn test_variants() {
+        check_variants(&Day07, EXAMPLE);
     }
 
     #[test]
-    fn test_part2() {
-        assert_eq!(part2(&INPUT), 171692855075500);
+    fn test_input_sets() {
+        check_input_sets(&Day07);
     }
 }
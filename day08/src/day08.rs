@@ -1,10 +1,60 @@
-use common::read_file_as_lines;
+use common::dsu::DisjointSet;
+use common::{Answer, Day, Input, ParseError, read_input_or};
 use once_cell::sync::Lazy;
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 
-pub static INPUT: Lazy<Vec<String>> =
-    Lazy::new(|| read_file_as_lines("data/day08.txt").expect("Failed to load input"));
+pub static INPUT: Lazy<Vec<Box>> = Lazy::new(|| {
+    Day08
+        .parse(&read_input_or("day08.txt", generated_input))
+        .expect("Failed to parse input")
+});
+
+/// The example of the puzzle, made for 10 connections in part 1 instead of
+/// 1000.
+pub const EXAMPLE: &str = "\
+162,817,812
+57,618,57
+906,360,560
+592,479,940
+352,342,300
+466,668,158
+542,29,236
+431,825,988
+739,650,466
+52,470,668
+216,146,977
+819,987,18
+117,168,530
+805,96,715
+346,949,466
+970,615,88
+941,993,340
+862,61,35
+984,92,344
+425,690,689";
+
+/// 1000 junction boxes at pseudo-random places, which stand in for a missing
+/// input: the example is too small for the 1000 connections of part 1.
+pub fn generated_input() -> String {
+    let mut next = pseudo_random();
+    let mut coordinate = || next() % 100_000;
+    (0..1000)
+        .map(|_| format!("{},{},{}", coordinate(), coordinate(), coordinate()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Pseudo-random numbers (xorshift), the same sequence on every call.
+fn pseudo_random() -> impl FnMut() -> u64 {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    }
+}
 
 #[derive(Clone, Copy, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct Box {
@@ -23,32 +73,34 @@ impl Box {
     }
 }
 
-fn parse_boxes(input: &[String]) -> Result<Vec<Box>, String> {
+fn parse_boxes(input: &str) -> Result<Vec<Box>, ParseError> {
+    let input = Input::from(input);
     input
-        .iter()
+        .lines()
         .enumerate()
-        .map(|(id, line)| {
-            let mut parts = line.split(',');
-            let x = parts
-                .next()
-                .ok_or("Missing x")?
-                .parse()
-                .map_err(|_| "Invalid x")?;
-            let y = parts
-                .next()
-                .ok_or("Missing y")?
-                .parse()
-                .map_err(|_| "Invalid y")?;
-            let z = parts
-                .next()
-                .ok_or("Missing z")?
-                .parse()
-                .map_err(|_| "Invalid z")?;
-            Ok(Box { id, x, y, z })
-        })
+        .map(|(id, line)| parse_box(id, line).map_err(|e| e.in_line(id + 1, line)))
         .collect()
 }
 
+fn parse_box(id: usize, line: &str) -> Result<Box, ParseError> {
+    let mut parts = line.split(',');
+    let mut column = 1;
+    let mut coordinate = |name: &str| {
+        let part = parts
+            .next()
+            .ok_or_else(|| ParseError::new(format!("Missing {}", name)).at(line.len() + 1, ""))?;
+        let value = part
+            .parse()
+            .map_err(|_| ParseError::new(format!("Invalid {}", name)).at(column, part));
+        column += part.len() + 1;
+        value
+    };
+    let x = coordinate("x")?;
+    let y = coordinate("y")?;
+    let z = coordinate("z")?;
+    Ok(Box { id, x, y, z })
+}
+
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
 struct DistanceTriplet {
     distance: u64,
@@ -76,142 +128,112 @@ fn sorted_distances(boxes: &[Box]) -> BinaryHeap<Reverse<DistanceTriplet>> {
     distances
 }
 
-pub fn part1(input: &[String], connections: usize) -> usize {
-    let boxes = parse_boxes(input).unwrap();
-    let mut distances = sorted_distances(&boxes);
-    let mut circuits = UnionFind::new(boxes.len());
+/// `None` if there are fewer pairs of boxes than `connections`.
+pub fn part1(boxes: &[Box], connections: usize) -> Option<usize> {
+    let mut distances = sorted_distances(boxes);
+    let mut circuits = DisjointSet::new(boxes.len());
     for _ in 0..connections {
         let Reverse(DistanceTriplet {
             distance: _,
             b1,
             b2,
-        }) = distances.pop().expect("Distances empty");
+        }) = distances.pop()?;
         circuits.union(b1.id, b2.id);
     }
-    let mut sizes = circuits.size.iter().collect::<BinaryHeap<_>>();
-    let s1 = sizes.pop().unwrap();
-    let s2 = sizes.pop().unwrap();
-    let s3 = sizes.pop().unwrap();
-    s1 * s2 * s3
+    Some(circuits.sizes().iter().take(3).product())
 }
 
-pub fn part2(input: &[String]) -> u64 {
-    let boxes = parse_boxes(input).unwrap();
-    let mut distances = sorted_distances(&boxes);
-    let mut circuits = UnionFind::new(boxes.len());
+/// `None` if there are fewer than two boxes, so nothing to connect.
+pub fn part2(boxes: &[Box]) -> Option<u64> {
+    let mut distances = sorted_distances(boxes);
+    let mut circuits = DisjointSet::new(boxes.len());
     let mut last_two = None;
-    let mut connections = 0;
-    while connections != boxes.len() - 1 {
+    while circuits.count() > 1 {
         let Reverse(DistanceTriplet {
             distance: _,
             b1,
             b2,
-        }) = distances.pop().expect("Distances empty");
-        if circuits.find(b1.id) != circuits.find(b2.id) {
+        }) = distances.pop()?;
+        if circuits.union(b1.id, b2.id) {
             last_two = Some(b1.x as u64 * b2.x as u64);
-            circuits.union(b1.id, b2.id);
-            connections += 1;
         }
     }
-    last_two.unwrap()
+    last_two
 }
 
-struct UnionFind {
-    parent: Vec<usize>,
-    size: Vec<usize>,
-}
+pub struct Day08;
 
-impl UnionFind {
-    fn new(n: usize) -> Self {
-        Self {
-            parent: Vec::from_iter(0..n),
-            size: vec![1; n],
-        }
+impl Day for Day08 {
+    type Model = Vec<Box>;
+
+    fn day(&self) -> u32 {
+        8
     }
 
-    fn find(&mut self, i: usize) -> usize {
-        if self.parent[i] != i {
-            self.parent[i] = self.find(self.parent[i]);
-        }
-        self.parent[i]
+    fn title(&self) -> &'static str {
+        "Playground"
     }
 
-    fn union(&mut self, i: usize, j: usize) {
-        let mut pi = self.find(i);
-        let mut pj = self.find(j);
-        if pi != pj { // faster w/o path compression in the example code
-            self.parent[pj] = pi;
-            self.size[pi] += self.size[pj];
-            self.size[pj] = 0; // needed because I use the sizes to get group sizes
-        }
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse_boxes(input)
+    }
+
+    fn part1(&self, boxes: &Self::Model) -> Answer {
+        let answer = part1(boxes, 1000).ok_or("Fewer than 1000 pairs of boxes")?;
+        Ok(answer.to_string())
+    }
+
+    fn part2(&self, boxes: &Self::Model) -> Option<Answer> {
+        let answer = part2(boxes).ok_or("Fewer than two boxes");
+        Some(answer.map(|answer| answer.to_string()).map_err(Into::into))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{check_answer, check_input_sets};
 
     #[test]
     fn test_example_part1() {
-        let input = "\
-162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689";
-        let input = input.lines().map(|s| s.to_string()).collect::<Vec<_>>();
-        assert_eq!(part1(&input, 10), 40);
+        let boxes = Day08.parse(EXAMPLE).unwrap();
+        assert_eq!(part1(&boxes, 10), Some(40));
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let e = parse_boxes("162,817,812\n57,6x8,57").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 4, "6x8"));
+        let e = parse_boxes("162,817").unwrap_err();
+        assert_eq!((e.line, e.message.as_str()), (1, "Missing z"));
+    }
+
+    #[test]
+    fn test_too_few_boxes() {
+        let boxes = Day08.parse("162,817,812").unwrap();
+        assert_eq!(part1(&boxes, 10), None);
+        assert_eq!(part2(&boxes), None);
+        assert!(Day::part1(&Day08, &boxes).is_err());
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&INPUT, 1000), 57564);
+        check_answer(8, 1, || part1(&INPUT, 1000).unwrap());
     }
 
     #[test]
     fn test_example_part2() {
-        let input = "\
-162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689";
-        let input = input.lines().map(|s| s.to_string()).collect::<Vec<_>>();
-        assert_eq!(part2(&input), 25272);
+        let boxes = Day08.parse(EXAMPLE).unwrap();
+        assert_eq!(part2(&boxes), Some(25272));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&INPUT), 133296744);
+        check_answer(8, 2, || part2(&INPUT).unwrap());
+    }
+
+    #[test]
+    fn test_input_sets() {
+        check_input_sets(&Day08);
     }
 }
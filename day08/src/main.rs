@@ -1,7 +1,6 @@
-mod day08;
-use crate::day08::{INPUT, part1, part2};
+use day08::day08::Day08;
+use std::error::Error;
 
-fn main() {
-    println!("Part1: {}", part1(&INPUT, 1000));
-    println!("Part2: {}", part2(&INPUT));
-}
\ No newline at end of file
+fn main() -> Result<(), Box<dyn Error>> {
+    common::solution::run(&Day08)
+}
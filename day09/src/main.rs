@@ -1,7 +1,6 @@
-mod day09;
-use crate::day09::{INPUT, part1, part2};
+use day09::day09::Day09;
+use std::error::Error;
 
-fn main() {
-    println!("Part1: {}", part1(&INPUT));
-    println!("Part2: {}", part2(&INPUT));
-}
\ No newline at end of file
+fn main() -> Result<(), Box<dyn Error>> {
+    common::solution::run(&Day09)
+}
@@ -1,13 +1,24 @@
 use bit_set::BitSet;
-use common::read_file_as_elements;
+use common::parse::{
+    self, Parser, bracketed, many, separated, spaces1, tag, take_while1, terminated, unsigned,
+};
+use common::{Answer, Day, ParseError, parse_lines, read_input_or};
 use microlp::{ComparisonOp, OptimizationDirection, Problem};
 use once_cell::sync::Lazy;
-use regex::Regex;
 use std::collections::{HashSet, VecDeque};
 use std::str::FromStr;
 
-pub static INPUT: Lazy<Vec<Machine>> =
-    Lazy::new(|| read_file_as_elements("data/day10.txt").expect("Failed to load input"));
+/// The example of the puzzle, which stands in for a missing input.
+pub const EXAMPLE: &str = "\
+[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+[...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+[.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+
+pub static INPUT: Lazy<Vec<Machine>> = Lazy::new(|| {
+    Day10
+        .parse(&read_input_or("day10.txt", || EXAMPLE.to_owned()))
+        .expect("Failed to parse input")
+});
 
 #[derive(Debug)]
 pub struct Machine {
@@ -17,42 +28,23 @@ pub struct Machine {
 }
 
 impl FromStr for Machine {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
         // [.##.] is tha target (. is false, # is true)
         // (3) (1,3) (2) (2,3) (0,2) (0,1) are the button wiring
         // {3,5,4,7} are the joltage_requirements
-
-        // Parse target: [.##.]
-        let target_re = Regex::new(r"\[([.#]+)]").unwrap();
-        let target_cap = target_re.captures(s).ok_or("Failed to parse target")?;
-        let target: Vec<bool> = target_cap[1].chars().map(|c| c == '#').collect();
-
-        // Parse button wiring: (3) (1,3) etc.
-        let wiring_re = Regex::new(r"\((\d+(?:,\d+)*)\)").unwrap();
-        let mut button_wiring: Vec<Vec<usize>> = Vec::new();
-
-        // Find where joltage requirements start to avoid parsing them as wiring
-        let joltage_start = s.find('{').unwrap_or(s.len());
-        let wiring_section = &s[..joltage_start];
-
-        for cap in wiring_re.captures_iter(wiring_section) {
-            let indices: Vec<usize> = cap[1].split(',').map(|n| n.parse().unwrap()).collect();
-            button_wiring.push(indices);
-        }
-
-        // Parse joltage requirements: {3,5,4,7}
-        let joltage_re = Regex::new(r"\{(\d+(?:,\d+)*)}").unwrap();
-        let joltage_cap = joltage_re
-            .captures(s)
-            .ok_or("Failed to parse joltage requirements")?;
-        let joltage_requirements: Vec<u32> = joltage_cap[1]
-            .split(',')
-            .map(|n| n.parse().unwrap())
-            .collect();
-
+        let target = bracketed("[", take_while1(|c| c == '.' || c == '#'), "]")
+            .map(|lights| lights.chars().map(|c| c == '#').collect());
+        let button = bracketed("(", separated(unsigned(), tag(",")), ")");
+        let joltage = bracketed("{", separated(unsigned(), tag(",")), "}");
+        let machine = (
+            terminated(target, spaces1()),
+            many(terminated(button, spaces1())),
+            joltage,
+        );
+        let (target, button_wiring, joltage_requirements) = parse::complete(s, machine)?;
         Ok(Machine {
             target,
             button_wiring,
@@ -136,9 +128,36 @@ pub fn part2(machines: &[Machine]) -> u32 {
     machines.iter().map(Machine::min_steps_to_joltage).sum()
 }
 
+pub struct Day10;
+
+impl Day for Day10 {
+    type Model = Vec<Machine>;
+
+    fn day(&self) -> u32 {
+        10
+    }
+
+    fn title(&self) -> &'static str {
+        "Factory"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(&self, machines: &Self::Model) -> Answer {
+        Ok(part1(machines).to_string())
+    }
+
+    fn part2(&self, machines: &Self::Model) -> Option<Answer> {
+        Some(Ok(part2(machines).to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{check_answer, check_input_sets};
 
     #[test]
     fn test_part1_machine1() {
@@ -161,9 +180,19 @@ mod tests {
         assert_eq!(machine.min_steps_to_target(), 2);
     }
 
+    #[test]
+    fn test_parse_error_location() {
+        let input = "[.##.] (3) (1,3) {3,5,4,7}\n[.##.] (3) (1,99999999999999999999) {3,5,4,7}";
+        let e = parse_lines::<Machine>(input).unwrap_err();
+        assert_eq!(
+            (e.line, e.column, e.text.as_str()),
+            (2, 15, "99999999999999999999")
+        );
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&INPUT), 466);
+        check_answer(10, 1, || part1(&INPUT));
     }
 
     #[test]
@@ -176,10 +205,10 @@ mod tests {
         let b4 = problem.add_integer_var(1.0, (0, 7));
         let b5 = problem.add_integer_var(1.0, (0, 7));
 
-        problem.add_constraint(&[(b4, 1.0), (b5, 1.0)], ComparisonOp::Eq, 3.0);
-        problem.add_constraint(&[(b1, 1.0), (b5, 1.0)], ComparisonOp::Eq, 5.0);
-        problem.add_constraint(&[(b2, 1.0), (b3, 1.0), (b4, 1.0)], ComparisonOp::Eq, 4.0);
-        problem.add_constraint(&[(b0, 1.0), (b1, 1.0), (b3, 1.0)], ComparisonOp::Eq, 7.0);
+        problem.add_constraint([(b4, 1.0), (b5, 1.0)], ComparisonOp::Eq, 3.0);
+        problem.add_constraint([(b1, 1.0), (b5, 1.0)], ComparisonOp::Eq, 5.0);
+        problem.add_constraint([(b2, 1.0), (b3, 1.0), (b4, 1.0)], ComparisonOp::Eq, 4.0);
+        problem.add_constraint([(b0, 1.0), (b1, 1.0), (b3, 1.0)], ComparisonOp::Eq, 7.0);
 
         let solution = problem.solve().unwrap();
         println!("Solution = {}", solution.objective());
@@ -216,6 +245,11 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&INPUT), 17214);
+        check_answer(10, 2, || part2(&INPUT));
+    }
+
+    #[test]
+    fn test_input_sets() {
+        check_input_sets(&Day10);
     }
 }
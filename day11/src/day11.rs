@@ -1,147 +1,158 @@
-use common::read_file_as_string;
+use common::graph::Digraph;
+use common::{Answer, Day, ParseError, read_input_or};
 use once_cell::sync::Lazy;
-use std::collections::HashMap;
 
-pub static INPUT: Lazy<String> =
-    Lazy::new(|| read_file_as_string("data/day11.txt").expect("Failed to load input"));
+/// The example of part 1, which stands in for a missing input.
+pub const EXAMPLE: &str = "\
+aaa: you hhh
+you: bbb ccc
+bbb: ddd eee
+ccc: ddd eee fff
+ddd: ggg
+eee: out
+fff: out
+ggg: out
+hhh: ccc fff iii
+iii: out";
+
+pub static INPUT: Lazy<Rack> = Lazy::new(|| {
+    Day11
+        .parse(&read_input_or("day11.txt", || EXAMPLE.to_owned()))
+        .expect("Failed to parse input")
+});
 
-struct Rack {
-    index: HashMap<String, usize>,
-    outputs: Vec<Vec<usize>>,
+/// The devices, each linked to those its outputs go to, with no cycles.
+#[derive(Debug, Clone)]
+pub struct Rack {
+    devices: Digraph<String>,
 }
 
 impl Rack {
-    fn new(input: &str) -> Self {
-        let mut index = HashMap::new();
-        let mut outputs = Vec::new();
-
-        for line in input.lines() {
-            let parts = line.split(':').collect::<Vec<_>>();
-            let from = parts[0].trim();
-            let from_i = intern(from, &mut index, &mut outputs);
-
-            let targets = parts
-                .get(1)
-                .map(|s| s.split_whitespace())
-                .into_iter()
-                .flatten();
-            for t in targets {
-                let to_i = intern(t, &mut index, &mut outputs);
-                outputs[from_i].push(to_i);
-            }
+    /// `None` if there are too many paths to count.
+    fn count(&self, from: &str, to: &str) -> Option<u64> {
+        match (self.devices.index_of(from), self.devices.index_of(to)) {
+            (Some(from), Some(to)) => self.devices.count_paths(from, to),
+            _ => Some(0),
         }
+    }
+}
 
-        Self {
-            index,
-            outputs,
+// each line is a device and the devices its outputs go to: `aaa: you hhh`
+fn parse_rack(input: &str) -> Result<Rack, ParseError> {
+    let mut devices = Digraph::new();
+    for (y, line) in input.lines().enumerate() {
+        let (from, targets) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::new("Missing ':'").in_line(y + 1, line))?;
+        let from = from.trim();
+        if from.is_empty() {
+            return Err(ParseError::new("Missing device").in_line(y + 1, line));
+        }
+        devices.intern(from.to_owned());
+        for to in targets.split_whitespace() {
+            devices.add_edge(from.to_owned(), to.to_owned());
         }
     }
-
-    fn count_paths(&self, from: &str, to: &str) -> usize {
-        // NOTE: I don't need to store the current path for the given data !!
-        let &from_i = match self.index.get(from) {
-            Some(i) => i,
-            None => return 0,
-        };
-        let &to_i = match self.index.get(to) {
-            Some(i) => i,
-            None => return 0,
-        };
-        let mut cache = HashMap::new();
-        Self::count_paths_inner(self, from_i, to_i, &mut cache)
+    if let Some(cycle) = devices.find_cycle() {
+        let names = cycle
+            .iter()
+            .map(|&node| devices.label(node).as_str())
+            .collect::<Vec<_>>();
+        // located at the outputs of its first device
+        let (y, line) = input
+            .lines()
+            .enumerate()
+            .find(|(_, line)| line.split(':').next().map(str::trim) == names.first().copied())
+            .unwrap_or_default();
+        return Err(
+            ParseError::new(format!("Cycle through {}", names.join(", "))).in_line(y + 1, line),
+        );
     }
+    Ok(Rack { devices })
+}
 
-    fn count_paths_inner(
-        &self,
-        from: usize,
-        to: usize,
-        cache: &mut HashMap<(usize, usize), usize>,
-    ) -> usize {
-        if from == to {
-            return 1;
-        }
-        if let Some(&v) = cache.get(&(from, to)) {
-            return v;
-        }
+/// `None` if there are too many paths to count.
+pub fn part1(rack: &Rack) -> Option<u64> {
+    rack.count("you", "out")
+}
 
-        let sum: usize = self
-            .outputs
-            .get(from)
-            .map(|v| {
-                v.iter()
-                    .filter_map(|&s| {
-                        Some(self.count_paths_inner(s, to, cache))
-                    })
-                    .sum()
-            })
-            .unwrap_or(0);
-
-        cache.insert((from, to), sum);
-        sum
-    }
+/// `None` if there are too many paths to count.
+pub fn part2(rack: &Rack) -> Option<u64> {
+    let leg1a = rack.count("svr", "fft")?;
+    let leg2a = rack.count("fft", "dac")?;
+    let leg3a = rack.count("dac", "out")?;
+    let fft_before_dac = leg1a.checked_mul(leg2a)?.checked_mul(leg3a)?;
+
+    let leg1b = rack.count("svr", "dac")?;
+    let leg2b = rack.count("dac", "fft")?;
+    let leg3b = rack.count("fft", "out")?;
+    let dac_before_fft = leg1b.checked_mul(leg2b)?.checked_mul(leg3b)?;
+
+    fft_before_dac.checked_add(dac_before_fft)
 }
 
-fn intern(
-    s: &str,
-    index: &mut HashMap<String, usize>,
-    outputs: &mut Vec<Vec<usize>>,
-) -> usize {
-    use std::collections::hash_map::Entry;
-    let i = index.len(); // cannot borrow after
-    match index.entry(s.to_string()) {
-        Entry::Occupied(o) => *o.get(),
-        Entry::Vacant(v) => {
-            v.insert(i);
-            outputs.push(Vec::new());
-            i
-        }
+fn answer(count: Option<u64>) -> Answer {
+    match count {
+        Some(count) => Ok(count.to_string()),
+        None => Err("Too many paths to count".into()),
     }
 }
 
-pub fn part1(input: &str) -> usize {
-    let rack = Rack::new(input);
-    rack.count_paths("you", "out")
-}
+pub struct Day11;
 
-pub fn part2(input: &str) -> usize {
-    let rack = Rack::new(input);
+impl Day for Day11 {
+    type Model = Rack;
 
-    let leg1a = rack.count_paths("svr", "fft");
-    let leg2a = rack.count_paths("fft", "dac");
-    let leg3a = rack.count_paths("dac", "out");
-    let fft_before_dac = leg1a * leg2a * leg3a;
+    fn day(&self) -> u32 {
+        11
+    }
+
+    fn title(&self) -> &'static str {
+        "Reactor"
+    }
 
-    let leg1b = rack.count_paths("svr", "dac");
-    let leg2b = rack.count_paths("dac", "fft");
-    let leg3b = rack.count_paths("fft", "out");
-    let dac_before_fft = leg1b * leg2b * leg3b;
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse_rack(input)
+    }
+
+    fn part1(&self, rack: &Self::Model) -> Answer {
+        answer(part1(rack))
+    }
 
-    fft_before_dac + dac_before_fft
+    fn part2(&self, rack: &Self::Model) -> Option<Answer> {
+        Some(answer(part2(rack)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{check_answer, check_input_sets};
 
     #[test]
     fn test_example_part1() {
-        let input = "\
-aaa: you hhh
-you: bbb ccc
-bbb: ddd eee
-ccc: ddd eee fff
-ddd: ggg
-eee: out
-fff: out
-ggg: out
-hhh: ccc fff iii
-iii: out";
-        assert_eq!(part1(input), 5);
+        assert_eq!(part1(&Day11.parse(EXAMPLE).unwrap()), Some(5));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_rack("aaa: bbb\nbbb out").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 2, column 1: Missing ':' in \"bbb out\""
+        );
+        let e = parse_rack("aaa: bbb\n : out").unwrap_err();
+        assert_eq!(e.message, "Missing device");
+        let e = parse_rack("aaa: bbb\nbbb: ccc out\nccc: aaa").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "line 1, column 1: Cycle through aaa, bbb, ccc in \"aaa: bbb\""
+        );
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&INPUT), 534);
+        check_answer(11, 1, || part1(&INPUT).unwrap());
     }
 
     #[test]
@@ -160,11 +171,16 @@ dac: fff
 fff: ggg hhh
 ggg: out
 hhh: out";
-        assert_eq!(part2(input), 2);
+        assert_eq!(part2(&Day11.parse(input).unwrap()), Some(2));
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(part2(&INPUT), 499645520864100);
+        check_answer(11, 2, || part2(&INPUT).unwrap());
+    }
+
+    #[test]
+    fn test_input_sets() {
+        check_input_sets(&Day11);
     }
 }
@@ -1,7 +1,6 @@
-mod day11;
-use crate::day11::{INPUT, part1, part2};
+use day11::day11::Day11;
+use std::error::Error;
 
-fn main() {
-    println!("Part1: {}", part1(&INPUT));
-    println!("Part2: {}", part2(&INPUT));
-}
\ No newline at end of file
+fn main() -> Result<(), Box<dyn Error>> {
+    common::solution::run(&Day11)
+}
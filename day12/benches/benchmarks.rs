@@ -1,10 +1,19 @@
-use day12::day12::{INPUT, part1};
+use common::{Day, read_input_or};
+use day12::day12::{Day12, EXAMPLE, INPUT, part1};
 
 fn main() {
     divan::main()
 }
 
+#[divan::bench]
+fn bench_parse(bencher: divan::Bencher) {
+    let input = read_input_or("day12.txt", || EXAMPLE.to_owned());
+    bencher.bench(|| Day12.parse(divan::black_box(&input)));
+}
+
 #[divan::bench]
 fn bench_part1() {
     part1(divan::black_box(&INPUT));
 }
+
+common::bench_input_sets!(Day12, part1);
@@ -1,9 +1,53 @@
-use common::read_file_as_string;
+use common::grid::Grid;
+use common::parse::{
+    self, Cursor, Parser, alt, key_value, lines, newline, preceded, sections, separated,
+    separated_pair, spaces1, tag, take_while1, unsigned,
+};
+use common::{Answer, Day, ParseError, read_input_or};
 use once_cell::sync::Lazy;
 use std::collections::HashSet;
 
-pub static INPUT: Lazy<String> =
-    Lazy::new(|| read_file_as_string("data/day12.txt").expect("Failed to load input"));
+/// The example of the puzzle without its last region, whose search is too
+/// slow, which stands in for a missing input.
+pub const EXAMPLE: &str = "\
+0:
+###
+##.
+##.
+
+1:
+###
+##.
+.##
+
+2:
+.##
+###
+##.
+
+3:
+##.
+###
+##.
+
+4:
+###
+#..
+###
+
+5:
+###
+.#.
+###
+
+4x4: 0 0 0 0 2 0
+12x5: 1 0 1 0 2 2";
+
+pub static INPUT: Lazy<ParsedProblems> = Lazy::new(|| {
+    Day12
+        .parse(&read_input_or("day12.txt", || EXAMPLE.to_owned()))
+        .expect("Failed to parse input")
+});
 
 const SHAPE_SIDE: usize = 3;
 
@@ -19,9 +63,9 @@ impl Shape {
 
     fn rotate_right(&self) -> Self {
         let mut out = [[false; SHAPE_SIDE]; SHAPE_SIDE];
-        for r in 0..SHAPE_SIDE {
-            for c in 0..SHAPE_SIDE {
-                out[c][SHAPE_SIDE - 1 - r] = self.0[r][c];
+        for (r, row) in self.0.iter().enumerate() {
+            for (c, &cell) in row.iter().enumerate() {
+                out[c][SHAPE_SIDE - 1 - r] = cell;
             }
         }
         Shape(out)
@@ -29,11 +73,9 @@ impl Shape {
 
     fn flip_horizontally(&self) -> Self {
         // horizontal mirror (flip left-right)
-        let mut out = [[false; SHAPE_SIDE]; SHAPE_SIDE];
-        for r in 0..SHAPE_SIDE {
-            for c in 0..SHAPE_SIDE {
-                out[r][SHAPE_SIDE - 1 - c] = self.0[r][c];
-            }
+        let mut out = self.0;
+        for row in out.iter_mut() {
+            row.reverse();
         }
         Shape(out)
     }
@@ -53,14 +95,14 @@ impl Shape {
 #[derive(Clone)]
 struct State {
     quantities: Vec<usize>,
-    available: Vec<Vec<bool>>,
+    available: Grid<bool>,
 }
 
 impl State {
     fn new(width: usize, height: usize, quantities: Vec<usize>) -> Self {
         Self {
             quantities,
-            available: vec![vec![true; width]; height],
+            available: Grid::filled(width, height, true),
         }
     }
 
@@ -71,7 +113,7 @@ impl State {
     fn admits(&self, x: usize, y: usize, s: &Shape) -> bool {
         for dx in 0..SHAPE_SIDE {
             for dy in 0..SHAPE_SIDE {
-                if s.0[dy][dx] && !self.available[y + dy][x + dx] {
+                if s.0[dy][dx] && !self.available[(x + dx, y + dy)] {
                     return false;
                 }
             }
@@ -83,7 +125,7 @@ impl State {
         for dx in 0..SHAPE_SIDE {
             for dy in 0..SHAPE_SIDE {
                 if s.0[dy][dx] {
-                    self.available[y + dy][x + dx] = false;
+                    self.available[(x + dx, y + dy)] = false;
                 }
             }
         }
@@ -93,7 +135,7 @@ impl State {
         for dx in 0..SHAPE_SIDE {
             for dy in 0..SHAPE_SIDE {
                 if s.0[dy][dx] {
-                    self.available[y + dy][x + dx] = true;
+                    self.available[(x + dx, y + dy)] = true;
                 }
             }
         }
@@ -123,9 +165,11 @@ impl Problem {
         }
     }
 
+    // none if the region is narrower or shorter than a shape
     fn possible_coordinates(&self) -> impl Iterator<Item = (usize, usize)> {
-        (0..=self.width - SHAPE_SIDE)
-            .flat_map(|x| (0..=self.height - SHAPE_SIDE).map(move |y| (x, y)))
+        let xs = 0..(self.width + 1).saturating_sub(SHAPE_SIDE);
+        let ys = 0..(self.height + 1).saturating_sub(SHAPE_SIDE);
+        xs.flat_map(move |x| ys.clone().map(move |y| (x, y)))
     }
 
     fn is_solvable(&self, shapes: &[Shape], shape_permutations: &Vec<HashSet<Shape>>) -> bool {
@@ -135,7 +179,7 @@ impl Problem {
         if self.is_trivially_insolvable(shapes) {
             return false;
         }
-        self.can_fit(&shape_permutations)
+        self.can_fit(shape_permutations)
     }
 
     fn is_trivially_solvable(&self) -> bool {
@@ -159,7 +203,7 @@ impl Problem {
         let mut state = State::new(self.width, self.height, self.quantities.clone());
         let mut order = (0..self.quantities.len()).collect::<Vec<_>>();
         order.sort_by_key(|&i| -(self.quantities[i] as i8));
-        dbg!(self.can_fit_rec(&mut state, &permutations, &order))
+        self.can_fit_rec(&mut state, permutations, &order)
     }
 
     fn can_fit_rec(
@@ -194,7 +238,7 @@ impl Problem {
 }
 
 #[derive(Debug)]
-struct ParsedProblems {
+pub struct ParsedProblems {
     shapes: Vec<Shape>,
     problems: Vec<Problem>,
 }
@@ -220,63 +264,87 @@ impl ParsedProblems {
     }
 }
 
-fn parse_problems(input: &str) -> ParsedProblems {
-    let mut shapes = Vec::new();
-    let mut problems = Vec::new();
-    let mut lines = input.lines().peekable();
+enum Section {
+    Shape(Shape),
+    Regions(Vec<Problem>),
+}
 
-    // Parse shapes (blocks starting with "N:")
-    while let Some(line) = lines.peek() {
-        let trimmed = line.trim();
-        if trimmed.contains('x') && trimmed.contains(':') {
-            break; // Start of problem lines
-        }
-        let line = lines.next().unwrap().trim();
-        if line.is_empty() || line.ends_with(':') {
-            continue; // Skip empty lines and shape headers like 0:
+// 0:
+// ###
+// ##.
+// ##.
+fn shape<'a>() -> impl Parser<'a, Shape> {
+    move |c: &mut Cursor<'a>| {
+        let row = take_while1(|ch| ch == '#' || ch == '.');
+        let start = c.offset();
+        let (_, rows) =
+            key_value(unsigned::<usize>(), ":", preceded(newline(), lines(row))).parse(c)?;
+        if rows.len() != SHAPE_SIDE || rows.iter().any(|row| row.len() != SHAPE_SIDE) {
+            return Err(c.error_at(start, 0, "Shape is not 3x3"));
         }
-        // First row of shape found, collect all 3 rows
         let mut grid = [[false; SHAPE_SIDE]; SHAPE_SIDE];
-        for (c, ch) in line.chars().enumerate() {
-            if c < SHAPE_SIDE {
-                grid[0][c] = ch == '#';
+        for (cells, row) in grid.iter_mut().zip(rows) {
+            for (cell, ch) in cells.iter_mut().zip(row.chars()) {
+                *cell = ch == '#';
             }
         }
-        for r in 1..SHAPE_SIDE {
-            if let Some(row_line) = lines.next() {
-                for (c, ch) in row_line.trim().chars().enumerate() {
-                    if c < SHAPE_SIDE {
-                        grid[r][c] = ch == '#';
-                    }
-                }
-            }
-        }
-        shapes.push(Shape(grid));
+        Ok(Shape(grid))
     }
+}
 
-    // Parse problem lines
-    for line in lines {
-        let line = line.trim();
-        if line.is_empty() {
-            continue;
-        }
-        let parts: Vec<&str> = line.split(':').collect();
-        let dims: Vec<usize> = parts[0].split('x').map(|s| s.parse().unwrap()).collect();
-        let quantities: Vec<usize> = parts[1].split_whitespace().map(|s| s.parse().unwrap()).collect();
+// 12x5: 1 0 1 0 2 2
+fn region<'a>() -> impl Parser<'a, Problem> {
+    let dims = separated_pair(unsigned(), tag("x"), unsigned());
+    key_value(dims, ":", separated(unsigned(), spaces1()))
+        .map(|((width, height), quantities)| Problem::new(width, height, quantities))
+}
 
-        problems.push(Problem::new(dims[0], dims[1], quantities));
+pub fn parse_problems(input: &str) -> Result<ParsedProblems, ParseError> {
+    let section = alt(
+        shape().map(Section::Shape),
+        lines(region()).map(Section::Regions),
+    );
+    let mut shapes = Vec::new();
+    let mut problems = Vec::new();
+    for section in parse::complete(input, sections(section))? {
+        match section {
+            Section::Shape(shape) => shapes.push(shape),
+            Section::Regions(regions) => problems.extend(regions),
+        }
     }
+    Ok(ParsedProblems::new(shapes, problems))
+}
 
-    ParsedProblems::new(shapes, problems)
+pub fn part1(problems: &ParsedProblems) -> usize {
+    problems.count_solvable()
 }
 
-pub fn part1(input: &str) -> usize {
-    parse_problems(input).count_solvable()
+pub struct Day12;
+
+impl Day for Day12 {
+    type Model = ParsedProblems;
+
+    fn day(&self) -> u32 {
+        12
+    }
+
+    fn title(&self) -> &'static str {
+        "Christmas Tree Farm"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse_problems(input)
+    }
+
+    fn part1(&self, problems: &Self::Model) -> Answer {
+        Ok(part1(problems).to_string())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{check_answer, check_input_sets};
 
     #[test]
     fn test_part1_manual_example() {
@@ -307,6 +375,12 @@ mod tests {
         // assert_eq!(parsed.count_solvable(), 0);
     }
 
+    #[test]
+    fn test_region_smaller_than_a_shape() {
+        let parsed = parse_problems("4:\n###\n#..\n###\n\n2x5: 1").unwrap();
+        assert_eq!(parsed.count_solvable(), 0);
+    }
+
     #[test]
     fn test_permutations1() {
         let all = Shape([[true, true, true], [true, true, true], [true, true, true]]);
@@ -375,6 +449,25 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_parse_problems() {
+        let input = "0:\n###\n##.\n##.\n\n1:\n###\n#..\n###\n\n4x4: 0 2\n12x5: 1 1\n";
+        let parsed = parse_problems(input).unwrap();
+        assert_eq!(
+            parsed.shapes[1],
+            Shape([[true, true, true], [true, false, false], [true, true, true]])
+        );
+        assert_eq!(parsed.problems.len(), 2);
+        assert_eq!(
+            (parsed.problems[1].width, parsed.problems[1].height),
+            (12, 5)
+        );
+        assert_eq!(parsed.problems[1].quantities, vec![1, 1]);
+
+        let e = parse_problems("0:\n###\n##.\n##.\n\n4x4: 0 x\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (6, 8, "x"));
+    }
+
     #[ignore = "Takes too long to run on problem 3"]
     #[test]
     fn test_part1_example() {
@@ -413,11 +506,16 @@ mod tests {
 12x5: 1 0 1 0 2 2
 12x5: 1 0 1 0 3 2
         ";
-        assert_eq!(part1(input), 2);
+        assert_eq!(part1(&parse_problems(input).unwrap()), 2);
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(part1(&INPUT), 510);
+        check_answer(12, 1, || part1(&INPUT));
+    }
+
+    #[test]
+    fn test_input_sets() {
+        check_input_sets(&Day12);
     }
 }
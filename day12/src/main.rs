@@ -1,6 +1,6 @@
-mod day12;
-use crate::day12::{INPUT, part1};
+use day12::day12::Day12;
+use std::error::Error;
 
-fn main() {
-    println!("Part1: {}", part1(&INPUT));
-}
\ No newline at end of file
+fn main() -> Result<(), Box<dyn Error>> {
+    common::solution::run(&Day12)
+}
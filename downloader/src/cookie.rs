@@ -0,0 +1,128 @@
+//! The session cookie kept in the user's configuration.
+
+use crate::error::{Error, Result};
+use crate::html;
+use crate::polite::Polite;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Directory of the user's configuration: `AOC_CONFIG_DIR` if set, otherwise
+/// `aoc` in `XDG_CONFIG_HOME` or in `~/.config`.
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = env::var_os("AOC_CONFIG_DIR") {
+        return Some(dir.into());
+    }
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(base.join("aoc"))
+}
+
+/// Where `cookie set` stores the cookie.
+pub fn cookie_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("cookie"))
+}
+
+/// The cookie as pasted from the browser, with or without `session=`.
+pub fn normalize(cookie: &str) -> Option<&str> {
+    let cookie = cookie.trim();
+    let cookie = cookie.strip_prefix("session=").unwrap_or(cookie);
+    let valid = !cookie.is_empty() && cookie.chars().all(|c| c.is_ascii_alphanumeric());
+    valid.then_some(cookie)
+}
+
+/// Writes `cookie` to `path`, readable and writable only by the user.
+pub fn store(path: &Path, cookie: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(Error::io(dir))?;
+    }
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // the mode only applies to new files
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+                .map_err(Error::io(path))?;
+        }
+    }
+    let mut file = options.open(path).map_err(Error::io(path))?;
+    writeln!(file, "{}", cookie).map_err(Error::io(path))
+}
+
+/// The stored cookie, if there is one.
+pub fn load(path: &Path) -> Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(normalize(&content).map(str::to_string)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(Error::io(path)(e)),
+    }
+}
+
+/// Asks the page of the event at `base` who is logged in with `cookie`.
+pub fn check(polite: &Polite, base: &str, cookie: &str) -> Result<String> {
+    let request = polite
+        .get(base)
+        .header("Cookie", format!("session={}", cookie));
+    logged_in_user(&polite.text(request)?).ok_or(Error::InvalidCookie)
+}
+
+/// The user named in the header of an AOC page, which only has one when
+/// logged in.
+pub fn logged_in_user(page: &str) -> Option<String> {
+    let user = html::elements(page, "div", |attrs| {
+        html::attr(attrs, "class") == Some("user")
+    });
+    let user = user.first()?;
+    // the star count follows the name
+    let name = user.split("<span").next().unwrap_or(user);
+    let name = html::text(name).trim().to_string();
+    (!name.is_empty()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(" session=53616c7465645f5f\n"),
+            Some("53616c7465645f5f")
+        );
+        assert_eq!(normalize("53616c7465645f5f"), Some("53616c7465645f5f"));
+        assert_eq!(normalize("a b"), None);
+        assert_eq!(normalize(""), None);
+    }
+
+    #[test]
+    fn test_store_and_load() {
+        let path = stub::temp_path("config").join("cookie");
+        assert_eq!(load(&path).unwrap(), None);
+        store(&path, "c00k1e").unwrap();
+        store(&path, "0th3r").unwrap();
+        assert_eq!(load(&path).unwrap().as_deref(), Some("0th3r"));
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_logged_in_user() {
+        let page = r#"<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1>
+            <nav><ul><li><a href="/2025/about">[About]</a></li></ul></nav>
+            <div class="user">Ada &amp; co <span class="star-count">24*</span></div></div></header>"#;
+        assert_eq!(logged_in_user(page).as_deref(), Some("Ada & co"));
+        let logged_out = r#"<header><nav><ul><li><a href="/2025/auth/login">[Log In]</a></li></ul></nav></header>"#;
+        assert_eq!(logged_in_user(logged_out), None);
+    }
+}
@@ -0,0 +1,83 @@
+use std::str::FromStr;
+
+/// Number of puzzles in the event of `year`: 25 until 2024, 12 since 2025.
+pub fn days_in(year: i32) -> u32 {
+    if year < 2025 { 25 } else { 12 }
+}
+
+/// Parses `dayNN`.
+pub fn parse_day(day: &str) -> Result<u32, String> {
+    day.strip_prefix("day")
+        .filter(|rest| rest.len() == 2 && rest.chars().all(|c| c.is_ascii_digit()))
+        .and_then(|rest| rest.parse::<u32>().ok())
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("could not parse day in {}", day))
+}
+
+/// The days given to a command: `all`, or a comma separated list of days
+/// (`day03`) and inclusive ranges (`day01..day12`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Days {
+    All,
+    List(Vec<u32>),
+}
+
+impl Days {
+    /// The days, sorted and without repetitions.
+    pub fn resolve(&self, year: i32) -> Vec<u32> {
+        match self {
+            Days::All => (1..=days_in(year)).collect(),
+            Days::List(days) => {
+                let mut days = days.clone();
+                days.sort_unstable();
+                days.dedup();
+                days
+            }
+        }
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Days::All);
+        }
+        let mut days = Vec::new();
+        for item in s.split(',').map(str::trim) {
+            match item.split_once("..") {
+                Some((first, last)) => {
+                    let (first, last) = (parse_day(first)?, parse_day(last)?);
+                    if first > last {
+                        return Err(format!("empty range of days {}", item));
+                    }
+                    days.extend(first..=last);
+                }
+                None => days.push(parse_day(item)?),
+            }
+        }
+        Ok(Days::List(days))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!("day07".parse(), Ok(Days::List(vec![7])));
+        assert_eq!(
+            "all".parse::<Days>().unwrap().resolve(2025),
+            (1..=12).collect::<Vec<_>>()
+        );
+        assert_eq!("all".parse::<Days>().unwrap().resolve(2016).len(), 25);
+        let days = "day05,day01..day03, day02".parse::<Days>().unwrap();
+        assert_eq!(days.resolve(2025), [1, 2, 3, 5]);
+        assert!("day03..day01".parse::<Days>().is_err());
+        assert!("day1".parse::<Days>().is_err());
+        assert!("day26".parse::<Days>().is_err());
+        assert!("day01..".parse::<Days>().is_err());
+    }
+}
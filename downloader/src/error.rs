@@ -0,0 +1,128 @@
+use crate::polite::format_duration;
+use reqwest::StatusCode;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Why the downloader could not do what it was asked.
+#[derive(Debug)]
+pub enum Error {
+    /// Neither `--cookie`, `AOC_COOKIE` nor `cookie set` gave a session
+    /// cookie.
+    MissingCookie,
+    /// The server does not accept the session cookie, or it is malformed.
+    InvalidCookie,
+    /// There is no home directory to keep the configuration in.
+    MissingConfigDir,
+    Locked {
+        day: u32,
+        wait: Duration,
+    },
+    /// The request could not be made or its reply read.
+    Http(reqwest::Error),
+    /// The server replied with an error status.
+    Status {
+        url: String,
+        status: StatusCode,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// The puzzle page had no description in it.
+    NoPuzzle {
+        day: u32,
+    },
+    /// A leaderboard that is not valid JSON, fetched or from `path`.
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    /// The data directory of the workspace could not be found.
+    Data(common::Error),
+}
+
+impl Error {
+    pub fn io(path: impl Into<PathBuf>) -> impl FnOnce(io::Error) -> Error {
+        let path = path.into();
+        move |source| Error::Io { path, source }
+    }
+
+    /// A distinct code for each kind of error, from 10 upwards so that they
+    /// do not clash with the outcomes of `submit`.
+    pub fn exit_code(&self) -> ExitCode {
+        ExitCode::from(match self {
+            Error::MissingCookie => 10,
+            Error::Locked { .. } => 11,
+            Error::Status {
+                status: StatusCode::NOT_FOUND,
+                ..
+            } => 12,
+            Error::Http(_) | Error::Status { .. } => 13,
+            Error::Io { .. } => 14,
+            Error::NoPuzzle { .. } => 15,
+            Error::Json { .. } => 16,
+            Error::InvalidCookie => 17,
+            Error::MissingConfigDir => 18,
+            Error::Data(_) => 19,
+        })
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::MissingCookie => write!(
+                f,
+                "no session cookie, set AOC_COOKIE, use --cookie or run `downloader cookie set`"
+            ),
+            Error::InvalidCookie => write!(
+                f,
+                "the session cookie is invalid or has expired, log in again and run `downloader cookie set`"
+            ),
+            Error::MissingConfigDir => {
+                write!(f, "no configuration directory, set AOC_CONFIG_DIR or HOME")
+            }
+            Error::Locked { day, wait } => write!(
+                f,
+                "day {} is still locked, it unlocks in {}",
+                day,
+                format_duration(*wait)
+            ),
+            Error::Http(e) => write!(f, "{}", e),
+            Error::Status { url, status } => write!(f, "{} replied {}", url, status),
+            Error::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            Error::NoPuzzle { day } => write!(f, "no puzzle description found for day {}", day),
+            Error::Json { path, source } => write!(f, "{}: {}", path.display(), source),
+            Error::Data(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Http(e) => Some(e),
+            Error::Io { source, .. } => Some(source),
+            Error::Json { source, .. } => Some(source),
+            Error::Data(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+impl From<common::Error> for Error {
+    fn from(e: common::Error) -> Self {
+        Error::Data(e)
+    }
+}
@@ -0,0 +1,227 @@
+//! Tests generated from the examples in a puzzle: the `<pre><code>` blocks
+//! and the highlighted (`<code><em>`) answers that follow them.
+
+use crate::html;
+use std::fmt::Write;
+
+/// A test of a part on an example.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExampleTest {
+    /// Index of the example, in the order of the page.
+    pub example: usize,
+    pub part: u32,
+    pub expected: Option<String>,
+    /// Whether the expected answer was attributed with confidence; the test
+    /// is ignored otherwise.
+    pub confident: bool,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    pub examples: Vec<String>,
+    pub tests: Vec<ExampleTest>,
+}
+
+/// Finds an example and its answer for each part in `descriptions`, the
+/// `<article class="day-desc">` blocks of the page.
+///
+/// A part is tested on its last example, or on the last one of part 1 if it
+/// has none, and expects the last highlighted answer after it. The answer is
+/// only trusted when the part has that single example (part 2 also trusts
+/// the single example of part 1) and looks like an answer.
+pub fn extract(descriptions: &[&str]) -> Examples {
+    let mut found = Examples::default();
+    // the example of the previous part, and whether it was its only one
+    let mut previous: Option<(usize, bool)> = None;
+    for (i, description) in descriptions.iter().enumerate() {
+        let blocks = description
+            .match_indices("<pre>")
+            .map(|(at, _)| at)
+            .collect::<Vec<_>>();
+        for block in html::elements(description, "pre", |_| true) {
+            found.examples.push(html::text(block));
+        }
+        let (example, single, after) = match blocks.last() {
+            Some(&at) => (found.examples.len() - 1, blocks.len() == 1, at),
+            None => match previous {
+                Some((example, single)) => (example, single, 0),
+                None => continue,
+            },
+        };
+        previous = Some((example, single));
+        let expected = highlighted(&description[after..]).pop();
+        let looks_like_answer = expected
+            .as_ref()
+            .is_some_and(|a| !a.is_empty() && a.len() <= 40 && !a.contains(char::is_whitespace));
+        found.tests.push(ExampleTest {
+            example,
+            part: i as u32 + 1,
+            confident: single && looks_like_answer,
+            expected,
+        });
+    }
+    found
+}
+
+// the text of every <code><em>...</em></code>
+fn highlighted(html: &str) -> Vec<String> {
+    html.split("<code><em>")
+        .skip(1)
+        .filter_map(|rest| rest.split_once("</em></code>"))
+        .map(|(answer, _)| html::text(answer))
+        .collect()
+}
+
+/// The source of `dayNN/tests/examples.rs` for the examples of `day`.
+pub fn render(day: u32, examples: &Examples) -> String {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "//! Generated by `downloader examples --day day{:02}` from the examples of the",
+        day
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "//! puzzle. The ignored tests need their expected answers checked by hand."
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "use common::Solution;").unwrap();
+    writeln!(out, "use day{:02}::day{:02}::Day{:02};", day, day, day).unwrap();
+    for (i, example) in examples.examples.iter().enumerate() {
+        if examples.tests.iter().any(|t| t.example == i) {
+            writeln!(out).unwrap();
+            writeln!(
+                out,
+                "const EXAMPLE_{}: &str = {};",
+                i + 1,
+                raw_string(example)
+            )
+            .unwrap();
+        }
+    }
+    for test in &examples.tests {
+        writeln!(out).unwrap();
+        writeln!(out, "#[test]").unwrap();
+        if !test.confident {
+            writeln!(out, "#[ignore = \"check the expected answer\"]").unwrap();
+        }
+        writeln!(
+            out,
+            "fn test_example_{}_part{}() {{",
+            test.example + 1,
+            test.part
+        )
+        .unwrap();
+        let call = match test.part {
+            1 => format!("Day{:02}.part1(EXAMPLE_{})", day, test.example + 1),
+            _ => format!("Day{:02}.part2(EXAMPLE_{}).unwrap()", day, test.example + 1),
+        };
+        let expected = test.expected.as_deref().unwrap_or("?");
+        writeln!(out, "    assert_eq!({}.unwrap(), {:?});", call, expected).unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+    out
+}
+
+// a raw string literal with as many #s as it needs
+fn raw_string(text: &str) -> String {
+    let hashes = (0..)
+        .find(|&n| !text.contains(&format!("\"{}", "#".repeat(n))))
+        .unwrap();
+    let hashes = "#".repeat(hashes.max(1));
+    format!("r{}\"{}\"{}", hashes, text, hashes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PART1: &str = "<h2>--- Day 5: Cafeteria ---</h2><p>For example:</p>\
+        <pre><code>3-5\n10-14\n\n1\n5\n</code></pre>\
+        <p>Ingredient ID <code>1</code> is <em>spoiled</em>, <code><em>5</em></code> is fresh.</p>\
+        <p>So, <code><em>1</em></code> of the available ingredient IDs are fresh.</p>";
+    const PART2: &str = "<h2 id=\"part2\">--- Part Two ---</h2>\
+        <p>In total, <code><em>8</em></code> ingredient IDs are fresh.</p>";
+
+    #[test]
+    fn test_extract() {
+        let examples = extract(&[PART1, PART2]);
+        assert_eq!(examples.examples, ["3-5\n10-14\n\n1\n5\n"]);
+        assert_eq!(
+            examples.tests,
+            [
+                ExampleTest {
+                    example: 0,
+                    part: 1,
+                    expected: Some("1".to_string()),
+                    confident: true
+                },
+                ExampleTest {
+                    example: 0,
+                    part: 2,
+                    expected: Some("8".to_string()),
+                    confident: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_when_unsure() {
+        let two_examples = "<pre><code>a</code></pre><p><code><em>1</em></code></p>\
+            <pre><code>b\"#</code></pre><p><code><em>2 or 3</em></code></p>";
+        let examples = extract(&[two_examples, "<p>No answer.</p>"]);
+        assert_eq!(examples.examples, ["a", "b\"#"]);
+        let tests = examples.tests;
+        assert_eq!((tests[0].example, tests[0].confident), (1, false));
+        assert_eq!(tests[0].expected.as_deref(), Some("2 or 3"));
+        assert_eq!((tests[1].example, tests[1].expected.as_deref()), (1, None));
+    }
+
+    #[test]
+    fn test_render() {
+        let examples = Examples {
+            examples: vec!["1\n\"#2\n".to_string(), "unused".to_string()],
+            tests: vec![
+                ExampleTest {
+                    example: 0,
+                    part: 1,
+                    expected: Some("3".to_string()),
+                    confident: true,
+                },
+                ExampleTest {
+                    example: 0,
+                    part: 2,
+                    expected: None,
+                    confident: false,
+                },
+            ],
+        };
+        assert_eq!(
+            render(7, &examples),
+            r###"//! Generated by `downloader examples --day day07` from the examples of the
+//! puzzle. The ignored tests need their expected answers checked by hand.
+
+use common::Solution;
+use day07::day07::Day07;
+
+const EXAMPLE_1: &str = r##"1
+"#2
+"##;
+
+#[test]
+fn test_example_1_part1() {
+    assert_eq!(Day07.part1(EXAMPLE_1).unwrap(), "3");
+}
+
+#[test]
+#[ignore = "check the expected answer"]
+fn test_example_1_part2() {
+    assert_eq!(Day07.part2(EXAMPLE_1).unwrap().unwrap(), "?");
+}
+"###
+        );
+    }
+}
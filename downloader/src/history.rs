@@ -0,0 +1,347 @@
+//! The answers submitted for each day and what AOC said of them, which are
+//! enough to know the verdict of some answers without submitting them.
+
+use crate::error::{Error, Result};
+use crate::submit::Outcome;
+use common::input::data_dir;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What AOC said of an answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    /// The verdict in `outcome`, if it is one.
+    pub fn of(outcome: &Outcome) -> Option<Self> {
+        match outcome {
+            Outcome::Correct => Some(Verdict::Correct),
+            Outcome::Incorrect => Some(Verdict::Incorrect),
+            Outcome::TooHigh => Some(Verdict::TooHigh),
+            Outcome::TooLow => Some(Verdict::TooLow),
+            _ => None,
+        }
+    }
+
+    pub fn outcome(self) -> Outcome {
+        match self {
+            Verdict::Correct => Outcome::Correct,
+            Verdict::Incorrect => Outcome::Incorrect,
+            Verdict::TooHigh => Outcome::TooHigh,
+            Verdict::TooLow => Outcome::TooLow,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            Verdict::Correct,
+            Verdict::Incorrect,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+        ]
+        .into_iter()
+        .find(|v| v.name() == name)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Guess {
+    /// Unix time it was submitted at.
+    pub time: u64,
+    pub part: u32,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Why an answer need not be submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Known {
+    pub verdict: Verdict,
+    pub reason: String,
+}
+
+/// The guesses of a day, kept in a file with a line per guess: time, part,
+/// verdict and answer, separated by tabs.
+#[derive(Clone, Debug)]
+pub struct History {
+    path: PathBuf,
+    guesses: Vec<Guess>,
+}
+
+/// Where the guesses of `day` of `year` are kept, `dayNN-YEAR.guesses` in the
+/// data directory (see `common::input::data_dir`): unlike the inputs, they
+/// tell the years apart.
+pub fn history_path(year: i32, day: u32) -> Result<PathBuf> {
+    Ok(data_dir()?.join(format!("day{:02}-{}.guesses", day, year)))
+}
+
+impl History {
+    /// The history in `path`, empty if there is no such file.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let content = match fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(Error::io(&path)(e)),
+        };
+        // lines we cannot read are left alone, not lost
+        let guesses = content.lines().filter_map(parse_guess).collect();
+        Ok(Self { path, guesses })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn guesses(&self, part: u32) -> impl Iterator<Item = &Guess> {
+        self.guesses.iter().filter(move |g| g.part == part)
+    }
+
+    /// Adds a guess made now and appends it to the file.
+    pub fn record(&mut self, part: u32, verdict: Verdict, answer: &str) -> Result<()> {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let guess = Guess {
+            time,
+            part,
+            verdict,
+            answer: answer.trim().to_string(),
+        };
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(Error::io(dir))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(Error::io(&self.path))?;
+        writeln!(
+            file,
+            "{}\t{}\t{}\t{}",
+            guess.time,
+            guess.part,
+            guess.verdict.name(),
+            guess.answer
+        )
+        .map_err(Error::io(&self.path))?;
+        self.guesses.push(guess);
+        Ok(())
+    }
+
+    /// The greatest answer known to be too low and the least known to be too
+    /// high, for numeric answers.
+    pub fn bounds(&self, part: u32) -> (Option<i128>, Option<i128>) {
+        let numbers = |verdict| {
+            self.guesses(part)
+                .filter(move |g| g.verdict == verdict)
+                .filter_map(|g| g.answer.parse::<i128>().ok())
+        };
+        (
+            numbers(Verdict::TooLow).max(),
+            numbers(Verdict::TooHigh).min(),
+        )
+    }
+
+    /// The verdict `answer` would get, if the history already tells.
+    pub fn judge(&self, part: u32, answer: &str) -> Option<Known> {
+        let answer = answer.trim();
+        if let Some(g) = self.guesses(part).find(|g| g.answer == answer) {
+            let reason = format!("already submitted on {}", format_time(g.time));
+            return Some(Known {
+                verdict: g.verdict,
+                reason,
+            });
+        }
+        if let Some(g) = self.guesses(part).find(|g| g.verdict == Verdict::Correct) {
+            let reason = format!("the answer is {}", g.answer);
+            return Some(Known {
+                verdict: Verdict::Incorrect,
+                reason,
+            });
+        }
+        let value = answer.parse::<i128>().ok()?;
+        match self.bounds(part) {
+            (Some(low), _) if value <= low => Some(Known {
+                verdict: Verdict::TooLow,
+                reason: format!("{} is already too low", low),
+            }),
+            (_, Some(high)) if value >= high => Some(Known {
+                verdict: Verdict::TooHigh,
+                reason: format!("{} is already too high", high),
+            }),
+            _ => None,
+        }
+    }
+
+    /// The guesses of `part` (or both parts) and the bounds they give.
+    pub fn report(&self, part: Option<u32>) -> String {
+        let mut out = String::new();
+        for part in [1, 2]
+            .into_iter()
+            .filter(|&p| part.is_none_or(|part| part == p))
+        {
+            writeln!(out, "Part {}", part).unwrap();
+            let mut any = false;
+            for g in self.guesses(part) {
+                any = true;
+                writeln!(
+                    out,
+                    "  {}  {:<9}  {}",
+                    format_time(g.time),
+                    g.verdict.name(),
+                    g.answer
+                )
+                .unwrap();
+            }
+            if !any {
+                writeln!(out, "  no guesses").unwrap();
+            }
+            match self.bounds(part) {
+                (None, None) => {}
+                (low, high) => {
+                    let show = |b: Option<i128>| b.map_or("?".to_string(), |b| b.to_string());
+                    writeln!(
+                        out,
+                        "  the answer is between {} and {}, both excluded",
+                        show(low),
+                        show(high)
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        out
+    }
+}
+
+fn parse_guess(line: &str) -> Option<Guess> {
+    let mut fields = line.splitn(4, '\t');
+    Some(Guess {
+        time: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        verdict: Verdict::from_name(fields.next()?)?,
+        answer: fields.next()?.to_string(),
+    })
+}
+
+/// A Unix time as `2025-12-01 05:00:00 UTC`.
+pub fn format_time(time: u64) -> String {
+    let (days, secs) = (time / 86_400, time % 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        secs / 3600,
+        secs / 60 % 60,
+        secs % 60
+    )
+}
+
+// the date of a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub;
+
+    fn history(lines: &str) -> History {
+        History {
+            path: PathBuf::new(),
+            guesses: lines.lines().filter_map(parse_guess).collect(),
+        }
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let path = stub::temp_path("history").join("day05.guesses");
+        let mut h = History::load(&path).unwrap();
+        h.record(1, Verdict::TooLow, "548\n").unwrap();
+        h.record(2, Verdict::Incorrect, "a b").unwrap();
+        let loaded = History::load(&path).unwrap();
+        assert_eq!(loaded.guesses, h.guesses);
+        assert_eq!(loaded.guesses(1).next().unwrap().answer, "548");
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_judge() {
+        let h = history(
+            "1764565800\t1\ttoo-low\t548
+1764565900\t1\ttoo-high\t900
+1764566000\t1\ttoo-low\t600
+1764566100\t1\tincorrect\t700
+1764566200\t2\tcorrect\tabc
+garbage",
+        );
+        assert_eq!(h.bounds(1), (Some(600), Some(900)));
+        let verdict = |part, answer| h.judge(part, answer).map(|k| k.verdict);
+        assert_eq!(verdict(1, "548"), Some(Verdict::TooLow));
+        assert_eq!(verdict(1, "599"), Some(Verdict::TooLow));
+        assert_eq!(verdict(1, "600"), Some(Verdict::TooLow));
+        assert_eq!(verdict(1, "1000"), Some(Verdict::TooHigh));
+        assert_eq!(verdict(1, "700"), Some(Verdict::Incorrect));
+        assert_eq!(verdict(1, "650"), None);
+        assert_eq!(verdict(1, "x"), None);
+        assert_eq!(verdict(2, "abc"), Some(Verdict::Correct));
+        assert_eq!(verdict(2, "abd"), Some(Verdict::Incorrect));
+        assert_eq!(
+            h.judge(1, "700").unwrap().reason,
+            "already submitted on 2025-12-01 05:15:00 UTC"
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let h = history("1764565800\t1\ttoo-low\t548\n1764565900\t1\ttoo-high\t900");
+        assert_eq!(
+            h.report(None),
+            "Part 1
+  2025-12-01 05:10:00 UTC  too-low    548
+  2025-12-01 05:11:40 UTC  too-high   900
+  the answer is between 548 and 900, both excluded
+Part 2
+  no guesses
+"
+        );
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_time(951_782_400), "2000-02-29 00:00:00 UTC");
+        assert_eq!(format_time(1_764_565_200), "2025-12-01 05:00:00 UTC");
+    }
+}
@@ -0,0 +1,183 @@
+//! Just enough HTML handling for the pages AOC serves.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// An opening tag with its name and its raw attributes.
+    Start(&'a str, &'a str),
+    End(&'a str),
+    /// Text between tags, with entities still encoded.
+    Text(&'a str),
+}
+
+/// Splits `html` into tags and text, dropping comments and declarations.
+pub fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(open) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if open > 0 {
+            tokens.push(Token::Text(&rest[..open]));
+        }
+        rest = &rest[open..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, after)| after);
+            continue;
+        }
+        let Some(close) = rest.find('>') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        let tag = rest[1..close].trim_end_matches('/');
+        rest = &rest[close + 1..];
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::End(name.trim()));
+        } else if !tag.starts_with('!') && !tag.starts_with('?') {
+            let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            tokens.push(Token::Start(name, attrs.trim()));
+        }
+    }
+    tokens
+}
+
+/// Value of the attribute `name` in the raw attributes of a tag.
+pub fn attr<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attrs;
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].split_whitespace().last().unwrap_or("");
+        let value = rest[eq + 1..].trim_start();
+        let (found, after) = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split_once(quote).unwrap_or((&value[1..], "")),
+            _ => value.split_once(char::is_whitespace).unwrap_or((value, "")),
+        };
+        if key.eq_ignore_ascii_case(name) {
+            return Some(found);
+        }
+        rest = after;
+    }
+    None
+}
+
+/// The contents of every `<tag ...>...</tag>` whose attributes satisfy
+/// `accept`. Accepted elements must not have elements of the same kind in
+/// them, but the others may.
+pub fn elements<'a>(html: &'a str, tag: &str, accept: impl Fn(&str) -> bool) -> Vec<&'a str> {
+    let open = format!("<{}", tag);
+    let close = format!("</{}>", tag);
+    let mut found = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find(&open) {
+        rest = &rest[start + open.len()..];
+        let Some(end_of_tag) = rest.find('>') else {
+            break;
+        };
+        let attrs = &rest[..end_of_tag];
+        // <article> but not <articles>
+        if !attrs.is_empty() && !attrs.starts_with(char::is_whitespace) {
+            continue;
+        }
+        rest = &rest[end_of_tag + 1..];
+        // look inside the elements that are not accepted
+        if accept(attrs) {
+            let end = rest.find(&close).unwrap_or(rest.len());
+            found.push(&rest[..end]);
+            rest = &rest[end..];
+        }
+    }
+    found
+}
+
+/// Replaces the character references in `text`.
+pub fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .map(|end| &rest[1..end + 1]);
+        let c = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => {
+                let code = entity.strip_prefix('#')?;
+                let code = match code.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => code.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+        match (c, entity) {
+            (Some(c), Some(entity)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The decoded text of `html`, without tags.
+pub fn text(html: &str) -> String {
+    tokens(html)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(decode(text)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_and_attributes() {
+        let html = "<!-- hi --><p class='x' id=a>1 &lt; 2<br/></p>";
+        assert_eq!(
+            tokens(html),
+            [
+                Token::Start("p", "class='x' id=a"),
+                Token::Text("1 &lt; 2"),
+                Token::Start("br", ""),
+                Token::End("p"),
+            ]
+        );
+        assert_eq!(attr("class='x' id=a", "id"), Some("a"));
+        assert_eq!(
+            attr("href=\"/2025/day/1\" target=_blank", "href"),
+            Some("/2025/day/1")
+        );
+        assert_eq!(attr("class='x'", "id"), None);
+    }
+
+    #[test]
+    fn test_elements_and_text() {
+        let html = "<article class=\"day-desc\"><p>a &amp; b</p></article><articles/>\
+                    <article><p>&#60;c&#x3e; &bogus;</p></article>";
+        let desc = elements(html, "article", |attrs| {
+            attr(attrs, "class") == Some("day-desc")
+        });
+        assert_eq!(desc, ["<p>a &amp; b</p>"]);
+        let all = elements(html, "article", |_| true);
+        assert_eq!(
+            all.iter().map(|a| text(a)).collect::<Vec<_>>(),
+            ["a & b", "<c> &bogus;"]
+        );
+    }
+}
@@ -0,0 +1,260 @@
+//! Private leaderboards: the JSON AOC serves for them and reports on it.
+
+use crate::polite::{Polite, format_duration, unlock_time};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// AOC asks not to fetch a leaderboard more often than this.
+pub const REFRESH: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Anonymous users have no name.
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u32,
+    #[serde(default)]
+    pub last_star_ts: u64,
+    /// Stars by day and then by part.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u32, Star>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Star {
+    /// Unix time it was got at.
+    pub get_star_ts: u64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    /// Time from the unlock of `day` to the star of `part`, if it got it.
+    pub fn solve_time(&self, year: i32, day: u32, part: u32) -> Option<Duration> {
+        let star = self.completion_day_level.get(&day)?.get(&part)?;
+        let got = UNIX_EPOCH + Duration::from_secs(star.get_star_ts);
+        Some(
+            got.duration_since(unlock_time(year, day))
+                .unwrap_or_default(),
+        )
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn year(&self) -> i32 {
+        self.event.parse().unwrap_or(2025)
+    }
+
+    /// Members by local score, then stars, then who got their last star
+    /// first.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by_key(|m| {
+            (
+                std::cmp::Reverse((m.local_score, m.stars)),
+                m.last_star_ts,
+                m.id,
+            )
+        });
+        members
+    }
+
+    // days on which any member got a star
+    fn days(&self) -> Vec<u32> {
+        let mut days = self
+            .members
+            .values()
+            .flat_map(|m| m.completion_day_level.keys().copied())
+            .collect::<Vec<_>>();
+        days.sort_unstable();
+        days.dedup();
+        days
+    }
+
+    /// The overall standings.
+    pub fn standings(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "Rank  Score  Stars  Name").unwrap();
+        for (rank, m) in self.ranked().iter().enumerate() {
+            writeln!(
+                out,
+                "{:>4}  {:>5}  {:>5}  {}",
+                rank + 1,
+                m.local_score,
+                m.stars,
+                m.display_name()
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    /// For each member with stars, the time each one took since the day
+    /// unlocked, and from part 1 to part 2.
+    pub fn star_times(&self) -> String {
+        let year = self.year();
+        let mut out = String::new();
+        for m in self.ranked().into_iter().filter(|m| m.stars > 0) {
+            writeln!(out, "{}", m.display_name()).unwrap();
+            writeln!(
+                out,
+                "Day  {:>12}  {:>12}  {:>12}",
+                "Part 1", "Part 2", "Delta"
+            )
+            .unwrap();
+            for &day in m.completion_day_level.keys() {
+                let part1 = m.solve_time(year, day, 1);
+                let part2 = m.solve_time(year, day, 2);
+                let delta = part1.zip(part2).map(|(p1, p2)| p2.saturating_sub(p1));
+                writeln!(
+                    out,
+                    "{:>3}  {:>12}  {:>12}  {:>12}",
+                    day,
+                    time(part1),
+                    time(part2),
+                    time(delta)
+                )
+                .unwrap();
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// For each day, the members who got a star ranked by their time to
+    /// part 2 and, for those who only have part 1, by their time to it.
+    pub fn day_rankings(&self) -> String {
+        let year = self.year();
+        let mut out = String::new();
+        for day in self.days() {
+            writeln!(out, "Day {}", day).unwrap();
+            let mut solvers = self
+                .members
+                .values()
+                .filter_map(|m| {
+                    let part1 = m.solve_time(year, day, 1)?;
+                    let part2 = m.solve_time(year, day, 2);
+                    Some((part2.is_none(), part2.unwrap_or(part1), m))
+                })
+                .collect::<Vec<_>>();
+            solvers.sort_by_key(|&(partial, time, m)| (partial, time, m.id));
+            for (rank, (partial, time, m)) in solvers.into_iter().enumerate() {
+                let stars = if partial { "*" } else { "**" };
+                writeln!(
+                    out,
+                    "{:>4}  {:<2}  {:>12}  {}",
+                    rank + 1,
+                    stars,
+                    format_duration(time),
+                    m.display_name()
+                )
+                .unwrap();
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+fn time(duration: Option<Duration>) -> String {
+    duration.map_or_else(|| "-".to_string(), format_duration)
+}
+
+/// Downloads the JSON of the private leaderboard `id` from the AOC instance
+/// at `base`.
+pub fn fetch(polite: &Polite, base: &str, cookie: &str, id: u64) -> crate::Result<String> {
+    let request = polite
+        .get(&format!("{}/leaderboard/private/view/{}.json", base, id))
+        .header("Cookie", format!("session={}", cookie));
+    polite.text(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // day 1 of 2025 unlocked at 1764565200, day 2 at 1764651600
+    const JSON: &str = r#"{
+        "event": "2025",
+        "owner_id": 1,
+        "members": {
+            "1": {"id": 1, "name": "Ada", "stars": 3, "local_score": 10, "global_score": 0,
+                  "last_star_ts": 1764652200,
+                  "completion_day_level": {
+                      "1": {"1": {"get_star_ts": 1764565800, "star_index": 1},
+                            "2": {"get_star_ts": 1764566100, "star_index": 2}},
+                      "2": {"1": {"get_star_ts": 1764652200, "star_index": 5}}}},
+            "2": {"id": 2, "name": null, "stars": 2, "local_score": 10, "global_score": 0,
+                  "last_star_ts": 1764569000,
+                  "completion_day_level": {
+                      "1": {"1": {"get_star_ts": 1764565500, "star_index": 3},
+                            "2": {"get_star_ts": 1764569000, "star_index": 4}}}},
+            "3": {"id": 3, "name": "Bob", "stars": 0, "local_score": 0, "global_score": 0,
+                  "last_star_ts": 0, "completion_day_level": {}}
+        }
+    }"#;
+
+    #[test]
+    fn test_standings() {
+        let board = Leaderboard::parse(JSON).unwrap();
+        assert_eq!(
+            board.standings(),
+            "Rank  Score  Stars  Name
+   1     10      3  Ada
+   2     10      2  (anonymous user #2)
+   3      0      0  Bob
+"
+        );
+    }
+
+    #[test]
+    fn test_star_times() {
+        let board = Leaderboard::parse(JSON).unwrap();
+        let ada = &board.members["1"];
+        assert_eq!(ada.solve_time(2025, 1, 2), Some(Duration::from_secs(900)));
+        assert_eq!(ada.solve_time(2025, 2, 2), None);
+        let times = board.star_times();
+        assert!(times.starts_with(
+            "Ada
+Day        Part 1        Part 2         Delta
+  1        10m 0s        15m 0s         5m 0s
+  2        10m 0s             -             -
+
+"
+        ));
+        assert!(!times.contains("Bob"));
+    }
+
+    #[test]
+    fn test_day_rankings() {
+        let board = Leaderboard::parse(JSON).unwrap();
+        assert_eq!(
+            board.day_rankings(),
+            "Day 1
+   1  **        15m 0s  Ada
+   2  **     1h 3m 20s  (anonymous user #2)
+
+Day 2
+   1  *         10m 0s  Ada
+
+"
+        );
+    }
+}
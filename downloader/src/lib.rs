@@ -0,0 +1,18 @@
+pub mod cookie;
+pub mod days;
+pub mod error;
+pub mod examples;
+pub mod history;
+pub mod html;
+pub mod leaderboard;
+pub mod polite;
+pub mod puzzle;
+pub mod submit;
+
+// shared with the CLI tests, which are the only ones using all of it
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../tests/stub/mod.rs"]
+mod stub;
+
+pub use error::{Error, Result};
@@ -1,59 +1,556 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use common::answers::{Answers, DEFAULT_INPUT};
+use common::input::data_dir;
+use downloader::cookie;
+use downloader::days::{Days, parse_day};
+use downloader::history::{History, Verdict, history_path};
+use downloader::leaderboard::{self, Leaderboard};
+use downloader::polite::{self, Polite, Throttle, format_duration};
+use downloader::submit::{self, Outcome};
+use downloader::{Error, Result, examples, puzzle};
 use reqwest::blocking::Client;
 use std::fs;
-use std::io::Write;
-use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
-/// A program to download input for a day in AOC
+const REQUEST_LOG: &str = ".requests.log";
+
+/// A program to download input and puzzles for a day in AOC and submit answers
 #[derive(Debug, Parser)]
 struct Args {
-    /// Day to download
-    #[clap(short, long)]
-    day: String,
+    #[command(subcommand)]
+    command: Command,
+
+    /// Year of the event
+    #[clap(long, global = true, env = "AOC_YEAR", default_value_t = 2025)]
+    year: i32,
+
+    /// Server to talk to, without the year
+    #[clap(
+        long,
+        global = true,
+        env = "AOC_BASE_URL",
+        default_value = "https://adventofcode.com"
+    )]
+    base_url: String,
+
+    /// Session cookie; the one stored by `cookie set` if absent
+    #[clap(long, global = true, env = "AOC_COOKIE", hide_env_values = true)]
+    cookie: Option<String>,
+
+    /// Contact (e.g. an email address) added to the user agent, as AOC asks
+    /// of automated tools
+    #[clap(long, global = true, env = "AOC_CONTACT")]
+    contact: Option<String>,
+
+    /// Minimum seconds between two requests
+    #[clap(long, global = true, env = "AOC_MIN_INTERVAL", default_value_t = Throttle::default().min_interval.as_secs_f64())]
+    min_interval: f64,
+
+    /// Retries of a request the server fails with a 5xx
+    #[clap(long, global = true, env = "AOC_RETRIES", default_value_t = Throttle::default().retries)]
+    retries: u32,
 }
 
-fn main() {
-    dotenv::dotenv().ok();
-    let cookie = env::var("AOC_COOKIE").expect("AOC_COOKIE undefined");
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Download the input of some days into the data directory
+    Download {
+        /// Days to download: dayNN, a range like day01..day12, a comma
+        /// separated list of both, or all
+        #[clap(short, long)]
+        day: Days,
 
-    let args = Args::parse();
-    let day = parse_day(&args.day).expect(&format!("could not parse day in {}", args.day));
+        /// Download the inputs again even if they are already there
+        #[clap(short, long)]
+        force: bool,
+
+        /// Downloads running at the same time
+        #[clap(short, long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=8))]
+        jobs: u32,
+    },
+    /// Convert the puzzle of a day to data/dayNN.md (keeping its HTML in
+    /// data/dayNN.html) and save its examples as data/dayNN.exampleK.txt;
+    /// does nothing once both parts are there
+    Puzzle {
+        /// Day to fetch
+        #[clap(short, long, value_parser = parse_day)]
+        day: u32,
+    },
+    /// Generate dayNN/tests/examples.rs, with a test per part on the
+    /// examples of the puzzle fetched by `puzzle`
+    Examples {
+        /// Day of the puzzle
+        #[clap(short, long, value_parser = parse_day)]
+        day: u32,
 
-    let client = Client::builder()
-        .user_agent("github.com/jmgimeno/aoc2016 downloader")
-        .build()
-        .unwrap();
+        /// Overwrite the tests if they are already there
+        #[clap(short, long)]
+        force: bool,
+    },
+    /// Show the standings and star times of a private leaderboard, fetched
+    /// at most every 15 minutes and cached in data/leaderboard-ID.json
+    Leaderboard {
+        /// Id of the leaderboard, the number at the end of its URL
+        #[clap(long)]
+        id: u64,
 
-    fs::create_dir_all("data").expect("could not create output directory");
+        /// Only use the cached copy
+        #[clap(long)]
+        offline: bool,
+    },
+    /// Manage the session cookie
+    Cookie {
+        #[command(subcommand)]
+        action: CookieAction,
+    },
+    /// Submit an answer and report whether it was right, unless the answers
+    /// submitted before already tell
+    Submit {
+        /// Day of the puzzle
+        #[clap(short, long, value_parser = parse_day)]
+        day: u32,
+
+        /// Part of the puzzle
+        #[clap(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+
+        /// Answer to submit
+        #[clap(short, long)]
+        answer: String,
+
+        /// Submit it even if the history already tells its verdict
+        #[clap(short, long)]
+        force: bool,
+    },
+    /// Show the answers submitted for a day and what they tell
+    History {
+        /// Day of the puzzle
+        #[clap(short, long, value_parser = parse_day)]
+        day: u32,
+
+        /// Part of the puzzle; both if absent
+        #[clap(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum CookieAction {
+    /// Store the cookie in the user configuration, readable only by the user
+    Set {
+        /// Value of the session cookie; read from stdin if absent, which
+        /// keeps it out of the shell history
+        value: Option<String>,
+    },
+    /// Check that the server accepts the cookie and show who it logs in
+    Check,
+}
+
+/// What every command needs to talk to the server.
+struct Session {
+    polite: Polite,
+    year: i32,
+    // base URL of the year, e.g. https://adventofcode.com/2025
+    base: String,
+    cookie: String,
+}
 
-    download_day(day, &client, &cookie);
+fn main() -> ExitCode {
+    dotenv::dotenv().ok();
+    let args = Args::parse();
+    match run(args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            e.exit_code()
+        }
+    }
 }
 
-fn parse_day(day: &str) -> Option<u32> {
-    day.strip_prefix("day").and_then(|rest| {
-        if rest.len() == 2 && rest.chars().all(|c| c.is_ascii_digit()) {
-            rest.parse::<u32>().ok()
-        } else {
-            None
+fn run(args: Args) -> Result<ExitCode> {
+    if let Command::Puzzle { day } | Command::Submit { day, .. } = args.command
+        && let Some(wait) = polite::locked_for(args.year, day)
+    {
+        return Err(Error::Locked { day, wait });
+    }
+    if let Command::Leaderboard { id, offline } = args.command {
+        return show_leaderboard(&args, id, offline);
+    }
+    if let Command::Examples { day, force } = args.command {
+        return generate_examples(day, force);
+    }
+    if let Command::History { day, part } = args.command {
+        let history = History::load(history_path(args.year, day)?)?;
+        print!("{}", history.report(part));
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Command::Cookie {
+        action: CookieAction::Set { ref value },
+    } = args.command
+    {
+        return set_cookie(value.as_deref());
+    }
+    let session = connect(&args)?;
+
+    match args.command {
+        Command::Download {
+            ref day,
+            force,
+            jobs,
+        } => {
+            let days = day.resolve(args.year);
+            download_days(&days, &session, force, jobs as usize)
         }
+        Command::Puzzle { day } => {
+            fetch_puzzle(day, &session)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Cookie {
+            action: CookieAction::Check,
+        } => {
+            let user = cookie::check(&session.polite, &session.base, &session.cookie)?;
+            println!("The cookie is valid, logged in as {}.", user);
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Leaderboard { .. }
+        | Command::Examples { .. }
+        | Command::History { .. }
+        | Command::Cookie { .. } => {
+            unreachable!("handled without a session")
+        }
+        Command::Submit {
+            day,
+            part,
+            answer,
+            force,
+        } => {
+            let mut history = History::load(history_path(args.year, day)?)?;
+            if !force && let Some(known) = history.judge(part, &answer) {
+                let outcome = known.verdict.outcome();
+                println!(
+                    "Day {} part {}: {} ({}), not submitted.",
+                    day, part, outcome, known.reason
+                );
+                return Ok(outcome.exit_code());
+            }
+            let outcome = submit::submit(
+                &session.polite,
+                &session.base,
+                &session.cookie,
+                day,
+                part,
+                &answer,
+            )?;
+            println!("Day {} part {}: {}", day, part, outcome);
+            if let Some(verdict) = Verdict::of(&outcome) {
+                history.record(part, verdict, &answer)?;
+            }
+            if outcome == Outcome::Correct
+                && let Err(e) = record_answer(day, part, &answer)
+            {
+                eprintln!("Error recording the answer: {}", e);
+            }
+            // part 2 has just unlocked
+            if part == 1
+                && outcome == Outcome::Correct
+                && let Err(e) = fetch_puzzle(day, &session)
+            {
+                eprintln!("Error fetching part 2: {}", e);
+            }
+            Ok(outcome.exit_code())
+        }
+    }
+}
+
+// for the tests and `aoc2025 verify`, as the answer for dayNN.txt, in the
+// same answers.toml they read
+fn record_answer(day: u32, part: u32, answer: &str) -> common::Result<()> {
+    let mut answers = Answers::load()?;
+    answers.set(day, part, DEFAULT_INPUT, answer);
+    answers.save()
+}
+
+fn connect(args: &Args) -> Result<Session> {
+    let stored = match cookie::cookie_path() {
+        Some(path) if args.cookie.is_none() => cookie::load(&path)?,
+        _ => None,
+    };
+    let cookie = args.cookie.clone().or(stored).ok_or(Error::MissingCookie)?;
+    let client = Client::builder().user_agent(user_agent(args)).build()?;
+    let throttle = Throttle {
+        min_interval: Duration::from_secs_f64(args.min_interval.max(0.0)),
+        retries: args.retries,
+        ..Throttle::default()
+    };
+    Ok(Session {
+        polite: Polite::new(client, data_file(REQUEST_LOG)?, throttle),
+        year: args.year,
+        base: format!("{}/{}", args.base_url.trim_end_matches('/'), args.year),
+        cookie,
     })
 }
 
-fn download_day(day: u32, client: &Client, cookie: &String) {
-    let url = format!("https://adventofcode.com/2025/day/{}/input", day);
-    let resp = client
+/// The file called `name` in the data directory (see
+/// `common::input::data_dir`), which is created if it is not there.
+fn data_file(name: &str) -> Result<PathBuf> {
+    let dir = data_dir()?;
+    fs::create_dir_all(&dir).map_err(Error::io(&dir))?;
+    Ok(dir.join(name))
+}
+
+// e.g. "github.com/jmgimeno/aoc2025 downloader/0.1.0 (me@example.com)"
+fn user_agent(args: &Args) -> String {
+    let repository = env!("CARGO_PKG_REPOSITORY").trim_start_matches("https://");
+    let agent = format!("{} downloader/{}", repository, env!("CARGO_PKG_VERSION"));
+    match &args.contact {
+        Some(contact) => format!("{} ({})", agent, contact),
+        None => agent,
+    }
+}
+
+enum Status {
+    Downloaded,
+    Skipped,
+    Locked(Duration),
+    Failed(Error),
+}
+
+/// Downloads `days` with `jobs` threads and sums up how it went, unless
+/// there is a single day. Exits with the code of the first failure, if any.
+fn download_days(days: &[u32], session: &Session, force: bool, jobs: usize) -> Result<ExitCode> {
+    let next = AtomicUsize::new(0);
+    let statuses = Mutex::new(Vec::with_capacity(days.len()));
+    thread::scope(|s| {
+        for _ in 0..jobs.min(days.len()) {
+            s.spawn(|| {
+                while let Some(&day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let status = match polite::locked_for(session.year, day) {
+                        Some(wait) => Status::Locked(wait),
+                        None => match download_day(day, session, force) {
+                            Ok(true) => Status::Downloaded,
+                            Ok(false) => Status::Skipped,
+                            Err(e) => {
+                                eprintln!("Error downloading day {}: {}", day, e);
+                                Status::Failed(e)
+                            }
+                        },
+                    };
+                    statuses.lock().unwrap().push((day, status));
+                }
+            });
+        }
+    });
+    let mut statuses = statuses.into_inner().unwrap();
+    statuses.sort_by_key(|&(day, _)| day);
+
+    if statuses.len() > 1 {
+        print_summary(&statuses);
+    }
+    if let Some((_, Status::Failed(e))) = statuses
+        .iter()
+        .find(|(_, s)| matches!(s, Status::Failed(_)))
+    {
+        return Ok(e.exit_code());
+    }
+    match statuses.first() {
+        Some(&(day, Status::Locked(wait)))
+            if statuses.iter().all(|(_, s)| matches!(s, Status::Locked(_))) =>
+        {
+            Err(Error::Locked { day, wait })
+        }
+        _ => Ok(ExitCode::SUCCESS),
+    }
+}
+
+fn print_summary(statuses: &[(u32, Status)]) {
+    println!();
+    println!("Day  Status      Detail");
+    let mut counts = [0; 4];
+    for (day, status) in statuses {
+        let (i, name, detail) = match status {
+            Status::Downloaded => (0, "downloaded", String::new()),
+            Status::Skipped => (1, "skipped", "already there".to_string()),
+            Status::Locked(wait) => (
+                2,
+                "locked",
+                format!("unlocks in {}", format_duration(*wait)),
+            ),
+            Status::Failed(e) => (3, "failed", e.to_string()),
+        };
+        counts[i] += 1;
+        println!("{:>3}  {:<10}  {}", day, name, detail);
+    }
+    println!(
+        "{} downloaded, {} skipped, {} locked, {} failed",
+        counts[0], counts[1], counts[2], counts[3]
+    );
+}
+
+/// Downloads the input of `day`; false if it was already there.
+fn download_day(day: u32, session: &Session, force: bool) -> Result<bool> {
+    let filename = data_file(&format!("day{:02}.txt", day))?;
+    if !force && fs::exists(&filename).unwrap_or(false) {
+        println!(
+            "Day {} already downloaded (use --force to download it again).",
+            day
+        );
+        return Ok(false);
+    }
+    let url = format!("{}/day/{}/input", session.base, day);
+    let request = session
+        .polite
         .get(&url)
-        .header("Cookie", format!("session={}", cookie))
-        .send()
-        .unwrap();
-
-    if resp.status().is_success() {
-        let content = resp.text().unwrap();
-        let filename = format!("data/day{:02}.txt", day);
-        let mut file = fs::File::create(&filename).unwrap();
-        file.write_all(content.as_bytes()).unwrap();
-        println!("Day {} downloaded.", day);
+        .header("Cookie", format!("session={}", session.cookie));
+    let content = session.polite.text(request)?;
+    fs::write(&filename, content).map_err(Error::io(&filename))?;
+    println!("Day {} downloaded.", day);
+    Ok(true)
+}
+
+fn fetch_puzzle(day: u32, session: &Session) -> Result<()> {
+    let markdown_file = data_file(&format!("day{:02}.md", day))?;
+    if fs::read_to_string(&markdown_file).is_ok_and(|md| puzzle::has_part_two(&md)) {
+        println!("Day {} puzzle already complete.", day);
+        return Ok(());
+    }
+    let page = puzzle::fetch(&session.polite, &session.base, &session.cookie, day)?;
+    let descriptions = puzzle::descriptions(&page);
+    if descriptions.is_empty() {
+        return Err(Error::NoPuzzle { day });
+    }
+    let markdown = puzzle::to_markdown(&descriptions);
+    fs::write(&markdown_file, &markdown).map_err(Error::io(&markdown_file))?;
+    let html_file = data_file(&format!("day{:02}.html", day))?;
+    fs::write(&html_file, descriptions.join("\n")).map_err(Error::io(&html_file))?;
+    let examples = puzzle::examples(&descriptions);
+    for (i, example) in examples.iter().enumerate() {
+        let example_file = data_file(&format!("day{:02}.example{}.txt", day, i + 1))?;
+        fs::write(&example_file, example).map_err(Error::io(&example_file))?;
+    }
+    let parts = if puzzle::has_part_two(&markdown) {
+        2
     } else {
-        println!("Error downloading day {}: {}", day, resp.status());
+        1
+    };
+    println!(
+        "Day {} puzzle ({} part{}, {} examples) downloaded.",
+        day,
+        parts,
+        if parts == 1 { "" } else { "s" },
+        examples.len()
+    );
+    Ok(())
+}
+
+fn show_leaderboard(args: &Args, id: u64, offline: bool) -> Result<ExitCode> {
+    let cache = data_file(&format!("leaderboard-{}.json", id))?;
+    let age = fs::metadata(&cache)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(|modified| {
+            SystemTime::now()
+                .duration_since(modified)
+                .unwrap_or_default()
+        });
+    let json = match age {
+        Some(_) if offline => read_cache(&cache)?,
+        Some(age) if age < leaderboard::REFRESH => {
+            println!("Using the copy from {} ago.", format_duration(age));
+            read_cache(&cache)?
+        }
+        _ if offline => {
+            return Err(Error::Io {
+                path: cache,
+                source: std::io::ErrorKind::NotFound.into(),
+            });
+        }
+        _ => match fetch_leaderboard(args, id) {
+            Ok(json) => {
+                // only cache what can be read back
+                Leaderboard::parse(&json).map_err(|source| Error::Json {
+                    path: cache.clone(),
+                    source,
+                })?;
+                fs::write(&cache, &json).map_err(Error::io(&cache))?;
+                json
+            }
+            Err(e) if age.is_some() => {
+                eprintln!(
+                    "Error fetching the leaderboard, using the cached copy: {}",
+                    e
+                );
+                read_cache(&cache)?
+            }
+            Err(e) => return Err(e),
+        },
+    };
+    let board = Leaderboard::parse(&json).map_err(|source| Error::Json {
+        path: cache,
+        source,
+    })?;
+    println!("{}", board.standings());
+    print!("{}", board.star_times());
+    print!("{}", board.day_rankings());
+    Ok(ExitCode::SUCCESS)
+}
+
+fn fetch_leaderboard(args: &Args, id: u64) -> Result<String> {
+    let session = connect(args)?;
+    leaderboard::fetch(&session.polite, &session.base, &session.cookie, id)
+}
+
+fn read_cache(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(Error::io(path))
+}
+
+fn set_cookie(value: Option<&str>) -> Result<ExitCode> {
+    let value = match value {
+        Some(value) => value.to_string(),
+        None => {
+            let mut line = String::new();
+            io::stdin()
+                .read_line(&mut line)
+                .map_err(Error::io("stdin"))?;
+            line
+        }
+    };
+    let value = cookie::normalize(&value).ok_or(Error::InvalidCookie)?;
+    let path = cookie::cookie_path().ok_or(Error::MissingConfigDir)?;
+    cookie::store(&path, value)?;
+    println!("Cookie stored in {}.", path.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+fn generate_examples(day: u32, force: bool) -> Result<ExitCode> {
+    let html_file = data_file(&format!("day{:02}.html", day))?;
+    let page = fs::read_to_string(&html_file).map_err(Error::io(&html_file))?;
+    let found = examples::extract(&puzzle::descriptions(&page));
+    if found.tests.is_empty() {
+        return Err(Error::NoPuzzle { day });
+    }
+    let tests_dir = format!("day{:02}/tests", day);
+    let tests_file = format!("{}/examples.rs", tests_dir);
+    if !force && fs::exists(&tests_file).unwrap_or(false) {
+        println!(
+            "{} already exists (use --force to overwrite it).",
+            tests_file
+        );
+        return Ok(ExitCode::SUCCESS);
     }
+    fs::create_dir_all(&tests_dir).map_err(Error::io(&tests_dir))?;
+    fs::write(&tests_file, examples::render(day, &found)).map_err(Error::io(&tests_file))?;
+    let ignored = found.tests.iter().filter(|t| !t.confident).count();
+    println!(
+        "{} written with {} tests, {} of them ignored until checked.",
+        tests_file,
+        found.tests.len(),
+        ignored
+    );
+    Ok(ExitCode::SUCCESS)
 }
@@ -0,0 +1,323 @@
+//! Keeps the downloader within what AOC asks of automated tools: no locked
+//! days, spaced out requests, and backing off when the server struggles.
+
+use crate::error::{Error, Result};
+use reqwest::StatusCode;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How often requests may be made, and how they are retried.
+#[derive(Clone, Copy, Debug)]
+pub struct Throttle {
+    /// Minimum time between two requests, also across runs.
+    pub min_interval: Duration,
+    /// Wait before retrying a request answered with a 5xx, doubled on each
+    /// retry.
+    pub backoff: Duration,
+    pub retries: u32,
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::from_secs(5),
+            backoff: Duration::from_secs(10),
+            retries: 3,
+        }
+    }
+}
+
+// entries kept in the request log when it is trimmed
+const LOG_ENTRIES: usize = 50;
+
+/// When the puzzle of `day` unlocks: midnight US Eastern, which is always
+/// 05:00 UTC in December.
+pub fn unlock_time(year: i32, day: u32) -> SystemTime {
+    let days = days_from_civil(year as i64, 12, day as i64);
+    let secs = days * 86_400 + 5 * 3_600;
+    UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)
+}
+
+/// Time left until `day` unlocks, or `None` if it already has.
+pub fn locked_for(year: i32, day: u32) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(SystemTime::now())
+        .ok()
+}
+
+// days since 1970-01-01 of a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Shows a duration as `1h 2m 3s`, leaving out leading zero units.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 3600, secs / 60 % 60, secs % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, s) => format!("{}m {}s", m, s),
+        (h, m, s) => format!("{}h {}m {}s", h, m, s),
+    }
+}
+
+/// An HTTP client that waits its turn before each request and writes it to
+/// a log, whose last entry is how the next run knows when it may start.
+///
+/// It can be shared by threads: each one reserves the next free turn, so
+/// their requests overlap but do not start closer than the interval.
+pub struct Polite {
+    client: Client,
+    log: PathBuf,
+    throttle: Throttle,
+    // earliest start of the next request of this run; also guards the log
+    next_turn: Mutex<Option<SystemTime>>,
+}
+
+impl Polite {
+    pub fn new(client: Client, log: impl Into<PathBuf>, throttle: Throttle) -> Self {
+        Self {
+            client,
+            log: log.into(),
+            throttle,
+            next_turn: Mutex::new(None),
+        }
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    /// Sends `request` once its turn comes, retrying it on 5xx replies.
+    pub fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        let mut backoff = self.throttle.backoff;
+        let mut attempt = 0;
+        loop {
+            self.wait_turn();
+            let attempt_request = request
+                .try_clone()
+                .expect("requests with streamed bodies cannot be retried");
+            let (method, url) = attempt_request
+                .try_clone()
+                .and_then(|r| r.build().ok())
+                .map(|r| (r.method().to_string(), r.url().to_string()))
+                .unwrap_or_default();
+            let response = attempt_request.send();
+            let status = match &response {
+                Ok(r) => r.status().as_u16().to_string(),
+                Err(_) => "error".to_string(),
+            };
+            self.record(&format!("{} {} {}", status, method, url));
+            match response {
+                Ok(r) if r.status().is_server_error() && attempt < self.throttle.retries => {
+                    println!(
+                        "{} {}, retrying in {}",
+                        url,
+                        r.status(),
+                        format_duration(backoff)
+                    );
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                response => return response,
+            }
+        }
+    }
+
+    /// Sends `request` like `send` and reads the body of a successful reply.
+    pub fn text(&self, request: RequestBuilder) -> Result<String> {
+        let response = self.send(request)?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response.text()?);
+        }
+        let url = response.url().to_string();
+        // what AOC replies to inputs asked for without a valid session
+        if status == StatusCode::BAD_REQUEST
+            && response.text()?.contains("Puzzle inputs differ by user")
+        {
+            return Err(Error::InvalidCookie);
+        }
+        Err(Error::Status { url, status })
+    }
+
+    // sleeps until min_interval has passed since the last logged request
+    // and since the turn taken by any other thread
+    fn wait_turn(&self) {
+        let interval = self.throttle.min_interval;
+        let start = {
+            let mut next_turn = self.next_turn.lock().unwrap();
+            let logged = self.last_request().map(|last| last + interval);
+            let start = [Some(SystemTime::now()), logged, *next_turn]
+                .into_iter()
+                .flatten()
+                .max()
+                .unwrap();
+            *next_turn = Some(start + interval);
+            start
+        };
+        if let Ok(wait) = start.duration_since(SystemTime::now()) {
+            thread::sleep(wait);
+        }
+    }
+
+    fn last_request(&self) -> Option<SystemTime> {
+        let log = fs::read_to_string(&self.log).ok()?;
+        let (millis, _) = log.lines().last()?.split_once(' ')?;
+        Some(UNIX_EPOCH + Duration::from_millis(millis.parse().ok()?))
+    }
+
+    // the log is only a throttle, so failing to write it is not an error
+    fn record(&self, entry: &str) {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis());
+        let line = format!("{} {}\n", millis, entry);
+        let _guard = self.next_turn.lock().unwrap();
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&self.log) {
+            let _ = file.write_all(line.as_bytes());
+        }
+        if let Ok(log) = fs::read_to_string(&self.log) {
+            let lines = log.lines().collect::<Vec<_>>();
+            if lines.len() > 2 * LOG_ENTRIES {
+                let kept = lines[lines.len() - LOG_ENTRIES..].join("\n");
+                let _ = fs::write(&self.log, kept + "\n");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::{self, Reply};
+    use std::time::Instant;
+
+    fn quick(min_interval: Duration) -> Throttle {
+        Throttle {
+            min_interval,
+            backoff: Duration::from_millis(1),
+            retries: 3,
+        }
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        let unlock = unlock_time(2025, 1).duration_since(UNIX_EPOCH).unwrap();
+        // 2025-12-01T05:00:00Z
+        assert_eq!(unlock.as_secs(), 1_764_565_200);
+        let next = unlock_time(2025, 2).duration_since(UNIX_EPOCH).unwrap();
+        assert_eq!(next - unlock, Duration::from_secs(86_400));
+        assert_eq!(locked_for(2015, 1), None);
+        assert!(locked_for(3000, 1).is_some());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(42)), "42s");
+        assert_eq!(format_duration(Duration::from_secs(65)), "1m 5s");
+        assert_eq!(format_duration(Duration::from_secs(7322)), "2h 2m 2s");
+    }
+
+    #[test]
+    fn test_retries_server_errors_and_logs_requests() {
+        let log = stub::temp_path("retries.log");
+        let (root, server) = stub::serve(vec![
+            Reply::status(503),
+            Reply::status(502),
+            Reply::ok("fine"),
+        ]);
+        let polite = Polite::new(Client::new(), &log, quick(Duration::ZERO));
+        let response = polite
+            .send(polite.get(&format!("{}/2025/day/1/input", root)))
+            .unwrap();
+        assert_eq!(response.text().unwrap(), "fine");
+        assert_eq!(server.join().unwrap().len(), 3);
+        let statuses = fs::read_to_string(&log)
+            .unwrap()
+            .lines()
+            .map(|l| l.split(' ').nth(1).unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(statuses, ["503", "502", "200"]);
+        fs::remove_file(log).unwrap();
+    }
+
+    #[test]
+    fn test_gives_up_after_retries() {
+        let log = stub::temp_path("gives-up.log");
+        let (root, server) = stub::serve(vec![
+            Reply::status(500);
+            quick(Duration::ZERO).retries as usize + 1
+        ]);
+        let polite = Polite::new(Client::new(), &log, quick(Duration::ZERO));
+        let response = polite.send(polite.get(&root)).unwrap();
+        assert_eq!(response.status().as_u16(), 500);
+        server.join().unwrap();
+        fs::remove_file(log).unwrap();
+    }
+
+    #[test]
+    fn test_waits_for_the_last_logged_request() {
+        let log = stub::temp_path("interval.log");
+        let (root, server) = stub::serve(vec![Reply::ok(""), Reply::ok("")]);
+        let polite = Polite::new(Client::new(), &log, quick(Duration::from_millis(300)));
+        polite.send(polite.get(&root)).unwrap();
+        let start = Instant::now();
+        // a second run reads the first one's log
+        let again = Polite::new(Client::new(), &log, quick(Duration::from_millis(300)));
+        again.send(again.get(&root)).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        server.join().unwrap();
+        fs::remove_file(log).unwrap();
+    }
+
+    #[test]
+    fn test_threads_take_turns() {
+        let log = stub::temp_path("threads.log");
+        let (root, server) = stub::serve(vec![Reply::ok(""); 3]);
+        let polite = Polite::new(Client::new(), &log, quick(Duration::from_millis(150)));
+        let start = Instant::now();
+        thread::scope(|s| {
+            for _ in 0..3 {
+                s.spawn(|| polite.send(polite.get(&root)).unwrap());
+            }
+        });
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        server.join().unwrap();
+        fs::remove_file(log).unwrap();
+    }
+
+    #[test]
+    fn test_detects_invalid_cookies() {
+        let log = stub::temp_path("invalid-cookie.log");
+        let logged_out = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+        let (root, server) = stub::serve(vec![
+            Reply {
+                status: 400,
+                body: logged_out.to_string(),
+            },
+            Reply::status(400),
+        ]);
+        let polite = Polite::new(Client::new(), &log, quick(Duration::ZERO));
+        let invalid = polite.text(polite.get(&root));
+        assert!(matches!(invalid, Err(Error::InvalidCookie)));
+        let other = polite.text(polite.get(&root));
+        assert!(matches!(other, Err(Error::Status { .. })));
+        server.join().unwrap();
+        fs::remove_file(log).unwrap();
+    }
+}
@@ -0,0 +1,212 @@
+use crate::error::Result;
+use crate::html::{self, Token};
+use crate::polite::Polite;
+
+/// Downloads the page of `day` from the AOC instance at `base`; it only has
+/// part 2 once part 1 is solved.
+pub fn fetch(polite: &Polite, base: &str, cookie: &str, day: u32) -> Result<String> {
+    let request = polite
+        .get(&format!("{}/day/{}", base, day))
+        .header("Cookie", format!("session={}", cookie));
+    polite.text(request)
+}
+
+/// The `<article class="day-desc">` blocks of a puzzle page, one per
+/// unlocked part.
+pub fn descriptions(page: &str) -> Vec<&str> {
+    html::elements(page, "article", |attrs| {
+        html::attr(attrs, "class")
+            .is_some_and(|class| class.split_whitespace().any(|c| c == "day-desc"))
+    })
+}
+
+/// Whether a description converted by `to_markdown` has part 2 in it.
+pub fn has_part_two(markdown: &str) -> bool {
+    markdown.lines().filter(|l| l.starts_with('#')).count() > 1
+}
+
+/// The contents of every `<pre><code>` block, in order.
+pub fn examples(descriptions: &[&str]) -> Vec<String> {
+    descriptions
+        .iter()
+        .flat_map(|d| html::elements(d, "pre", |_| true))
+        .map(html::text)
+        .collect()
+}
+
+/// Converts the descriptions to Markdown: the title of the first part
+/// becomes the title of the document and each later one a section.
+pub fn to_markdown(descriptions: &[&str]) -> String {
+    let mut w = Writer::default();
+    for description in descriptions {
+        for token in html::tokens(description) {
+            w.token(token);
+        }
+        w.flush();
+    }
+    let trimmed = w.out.trim_end().len();
+    w.out.truncate(trimmed);
+    w.out.push('\n');
+    w.out
+}
+
+#[derive(Default)]
+struct Writer<'a> {
+    out: String,
+    // inline text of the block being written
+    line: String,
+    headings: usize,
+    in_pre: bool,
+    in_code: usize,
+    links: Vec<&'a str>,
+}
+
+impl<'a> Writer<'a> {
+    fn token(&mut self, token: Token<'a>) {
+        match token {
+            Token::Start("p" | "h2" | "ul" | "li", _) => self.flush(),
+            Token::End("p") => self.flush(),
+            Token::End("h2") => {
+                let title = self.line.trim().trim_matches('-').trim().to_string();
+                let level = if self.headings == 0 { "#" } else { "##" };
+                self.out.push_str(&format!("{} {}\n\n", level, title));
+                self.headings += 1;
+                self.line.clear();
+            }
+            Token::End("li") => {
+                self.out.push_str(&format!("- {}\n", self.line.trim()));
+                self.line.clear();
+            }
+            Token::End("ul") => {
+                self.flush();
+                self.out.push('\n');
+            }
+            Token::Start("pre", _) => {
+                self.flush();
+                self.in_pre = true;
+            }
+            Token::End("pre") => {
+                let block = std::mem::take(&mut self.line);
+                let newline = if block.ends_with('\n') { "" } else { "\n" };
+                self.out
+                    .push_str(&format!("```\n{}{}```\n\n", block, newline));
+                self.in_pre = false;
+            }
+            Token::Start("code", _) | Token::End("code") if !self.in_pre => {
+                self.in_code = match token {
+                    Token::Start(..) => self.in_code + 1,
+                    _ => self.in_code.saturating_sub(1),
+                };
+                self.line.push('`');
+            }
+            Token::Start("em", _) | Token::End("em") if !self.in_pre && self.in_code == 0 => {
+                self.line.push('*');
+            }
+            Token::Start("a", attrs) => {
+                self.links.push(html::attr(attrs, "href").unwrap_or(""));
+                self.line.push('[');
+            }
+            Token::End("a") => {
+                let href = self.links.pop().unwrap_or("");
+                self.line.push_str(&format!("]({})", href));
+            }
+            Token::Text(text) => {
+                let text = html::decode(text);
+                if self.in_pre || self.in_code > 0 {
+                    self.line.push_str(&text);
+                } else {
+                    self.push_prose(&text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // whitespace collapsed and Markdown's own characters escaped
+    fn push_prose(&mut self, text: &str) {
+        for c in text.chars() {
+            if c.is_whitespace() {
+                if !self.line.ends_with(' ') && !self.line.is_empty() {
+                    self.line.push(' ');
+                }
+                continue;
+            }
+            if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']') {
+                self.line.push('\\');
+            }
+            self.line.push(c);
+        }
+    }
+
+    // ends a paragraph made of loose text
+    fn flush(&mut self) {
+        let text = self.line.trim();
+        if !text.is_empty() {
+            self.out.push_str(text);
+            self.out.push_str("\n\n");
+        }
+        self.line.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 5: Cafeteria ---</h2><p>The <a href="/2025/day/4">elves</a> have a <em>list</em> of IDs:</p>
+<pre><code>3-5
+10-14
+
+<em>1</em>
+</code></pre>
+<ul>
+<li>ID <code>1</code> is <em>spoiled</em>.</li>
+<li>Multiply: <code>2*3</code>  =  6_</li>
+</ul>
+<p>How many are <em>fresh</em>?</p>
+</article>
+<p>Your puzzle answer was <code>3</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now count <em>all</em> of them: <code>a &amp; b</code></p><pre><code>1-2</code></pre></article>
+</main>"#;
+
+    #[test]
+    fn test_to_markdown() {
+        let descriptions = descriptions(PAGE);
+        assert_eq!(descriptions.len(), 2);
+        let markdown = to_markdown(&descriptions);
+        assert_eq!(
+            markdown,
+            "# Day 5: Cafeteria
+
+The [elves](/2025/day/4) have a *list* of IDs:
+
+```
+3-5
+10-14
+
+1
+```
+
+- ID `1` is *spoiled*.
+- Multiply: `2*3` = 6\\_
+
+How many are *fresh*?
+
+## Part Two
+
+Now count *all* of them: `a & b`
+
+```
+1-2
+```
+"
+        );
+        assert!(has_part_two(&markdown));
+        assert!(!has_part_two(&to_markdown(&descriptions[..1])));
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(examples(&descriptions(PAGE)), ["3-5\n10-14\n\n1\n", "1-2"]);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::error::Result;
+use crate::html;
+use crate::polite::Polite;
+use std::fmt;
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// What AOC replied to a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently; `wait` is the time left, when
+    /// the reply says it.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    AlreadySolved,
+    /// A reply we do not know how to read, with its text.
+    Unrecognized(String),
+}
+
+impl Outcome {
+    /// 0 for a correct answer, and a distinct code from 3 upwards for each
+    /// other outcome (1 is kept for errors and 2 for bad arguments).
+    pub fn exit_code(&self) -> ExitCode {
+        ExitCode::from(match self {
+            Outcome::Correct => 0,
+            Outcome::Incorrect => 3,
+            Outcome::TooHigh => 4,
+            Outcome::TooLow => 5,
+            Outcome::RateLimited { .. } => 6,
+            Outcome::AlreadySolved => 7,
+            Outcome::Unrecognized(_) => 8,
+        })
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct!"),
+            Outcome::Incorrect => write!(f, "incorrect"),
+            Outcome::TooHigh => write!(f, "incorrect, too high"),
+            Outcome::TooLow => write!(f, "incorrect, too low"),
+            Outcome::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited, {}s left to wait", wait.as_secs())
+            }
+            Outcome::RateLimited { wait: None } => write!(f, "rate limited"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+            Outcome::Unrecognized(text) => write!(f, "unrecognized reply: {}", text),
+        }
+    }
+}
+
+/// POSTs `answer` for the given part of `day` to the AOC instance at `base`
+/// (e.g. `https://adventofcode.com/2025`) and reads the reply.
+pub fn submit(
+    polite: &Polite,
+    base: &str,
+    cookie: &str,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<Outcome> {
+    let url = format!("{}/day/{}/answer", base, day);
+    let level = part.to_string();
+    let request = polite
+        .post(&url)
+        .header("Cookie", format!("session={}", cookie))
+        .form(&[("level", level.as_str()), ("answer", answer)]);
+    Ok(parse_reply(&polite.text(request)?))
+}
+
+/// Reads the outcome from the HTML page AOC replies with.
+pub fn parse_reply(page: &str) -> Outcome {
+    let text = article_text(page);
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited {
+            wait: parse_wait(&text),
+        }
+    } else if text.contains("Did you already complete it") {
+        Outcome::AlreadySolved
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        }
+    } else {
+        Outcome::Unrecognized(text)
+    }
+}
+
+// the text of the <article> holding the message (or of the whole page), with
+// whitespace collapsed
+fn article_text(page: &str) -> String {
+    let article = html::elements(page, "article", |_| true);
+    let text = html::text(article.first().unwrap_or(&page));
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// "You have 1m 5s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (amount, _) = rest.split_once(" left to wait")?;
+    let mut secs = 0;
+    for part in amount.split_whitespace() {
+        let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let value = value.parse::<u64>().ok()?;
+        secs += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polite::Throttle;
+    use crate::stub::{self, Reply, page};
+    use reqwest::blocking::Client;
+    use std::fs;
+
+    #[test]
+    fn test_parse_reply() {
+        let cases = [
+            (
+                "That's the right answer!  You are <em>one gold star</em> closer.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data.",
+                Outcome::Incorrect,
+            ),
+            (
+                "That's not the right answer; your answer is too high.  Please wait one minute.",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Outcome::TooLow,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2025/day/3\">[Return to Day 3]</a>",
+                Outcome::RateLimited {
+                    wait: Some(Duration::from_secs(65)),
+                },
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Outcome::AlreadySolved,
+            ),
+            (
+                "Something  else\n entirely",
+                Outcome::Unrecognized("Something else entirely".to_string()),
+            ),
+        ];
+        for (message, outcome) in cases {
+            assert_eq!(parse_reply(&page(message)), outcome, "{}", message);
+        }
+    }
+
+    #[test]
+    fn test_submit_to_stub() {
+        let log = stub::temp_path("submit.log");
+        let (root, server) = stub::serve(vec![Reply::ok(page(
+            "That's not the right answer; your answer is too low.",
+        ))]);
+        let polite = Polite::new(Client::new(), &log, Throttle::default());
+        let outcome = submit(&polite, &format!("{}/2025", root), "c00k1e", 3, 2, "4 2").unwrap();
+        assert_eq!(outcome, Outcome::TooLow);
+        let request = &server.join().unwrap()[0];
+        assert!(request.starts_with("POST /2025/day/3/answer HTTP/1.1\r\n"));
+        assert!(request.contains("cookie: session=c00k1e\r\n"));
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=4+2"));
+        fs::remove_file(log).unwrap();
+    }
+}
@@ -0,0 +1,537 @@
+mod stub;
+
+use mock_aoc::clock::unlock_time;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::time::Duration;
+use stub::Reply;
+
+// A fresh directory to run the downloader in, removed when dropped.
+struct WorkDir(PathBuf);
+
+impl WorkDir {
+    fn new(name: &str) -> Self {
+        let dir = stub::temp_path(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        WorkDir(dir)
+    }
+
+    fn path(&self, file: &str) -> PathBuf {
+        self.0.join(file)
+    }
+
+    fn run(&self, args: &[&str], env: &[(&str, &str)]) -> Output {
+        run_in(&self.0, args, env)
+    }
+}
+
+impl Drop for WorkDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn run_in(dir: &Path, args: &[&str], env: &[(&str, &str)]) -> Output {
+    run_with_stdin(dir, args, env, "")
+}
+
+fn run_with_stdin(dir: &Path, args: &[&str], env: &[(&str, &str)], stdin: &str) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_downloader"));
+    // never the user's own configuration or data
+    command
+        .current_dir(dir)
+        .args(args)
+        .env("AOC_CONFIG_DIR", dir.join("config"))
+        .env("AOC_DATA_DIR", dir.join("data"));
+    for var in [
+        "AOC_YEAR",
+        "AOC_BASE_URL",
+        "AOC_COOKIE",
+        "AOC_CONTACT",
+        "AOC_RETRIES",
+        "AOC_MIN_INTERVAL",
+    ] {
+        command.env_remove(var);
+    }
+    command.envs(env.iter().copied());
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+#[test]
+fn test_downloads_canned_input() {
+    let dir = WorkDir::new("cli-download");
+    let (root, server) = stub::serve(vec![Reply::ok("1,2\n3,4\n")]);
+    let output = dir.run(
+        &[
+            "download",
+            "--day",
+            "day01",
+            "--base-url",
+            &root,
+            "--contact",
+            "me@example.com",
+        ],
+        &[("AOC_COOKIE", "s3ss10n")],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        fs::read_to_string(dir.path("data/day01.txt")).unwrap(),
+        "1,2\n3,4\n"
+    );
+
+    let request = &server.join().unwrap()[0];
+    assert!(request.starts_with("GET /2025/day/1/input HTTP/1.1\r\n"));
+    assert!(request.contains("cookie: session=s3ss10n\r\n"));
+    assert!(request.contains("user-agent: github.com/jmgimeno/aoc2025 downloader/"));
+    assert!(request.contains(" (me@example.com)\r\n"));
+
+    // the stub is gone, so this only works if nothing is requested
+    let again = dir.run(
+        &["download", "--day", "day01", "--base-url", &root],
+        &[("AOC_COOKIE", "s3ss10n")],
+    );
+    assert!(again.status.success(), "{}", stderr(&again));
+}
+
+#[test]
+fn test_downloads_into_the_data_dir() {
+    let dir = WorkDir::new("cli-data-dir");
+    let (root, server) = stub::serve(vec![Reply::ok("1,2\n")]);
+    let inputs = dir.path("inputs");
+    let output = dir.run(
+        &["download", "--day", "day01", "--base-url", &root],
+        &[
+            ("AOC_COOKIE", "s3ss10n"),
+            ("AOC_DATA_DIR", inputs.to_str().unwrap()),
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    server.join().unwrap();
+    assert_eq!(
+        fs::read_to_string(inputs.join("day01.txt")).unwrap(),
+        "1,2\n"
+    );
+    assert!(inputs.join(".requests.log").exists());
+    assert!(!dir.path("data").exists());
+}
+
+#[test]
+fn test_year_and_base_url_from_environment() {
+    let dir = WorkDir::new("cli-environment");
+    let (root, server) = stub::serve(vec![Reply::ok("input")]);
+    let output = dir.run(
+        &["download", "--day", "day07"],
+        &[
+            ("AOC_COOKIE", "s3ss10n"),
+            ("AOC_YEAR", "2016"),
+            ("AOC_BASE_URL", &format!("{}/", root)),
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let request = &server.join().unwrap()[0];
+    assert!(request.starts_with("GET /2016/day/7/input HTTP/1.1\r\n"));
+}
+
+#[test]
+fn test_not_found() {
+    let dir = WorkDir::new("cli-not-found");
+    let (root, server) = stub::serve(vec![Reply::status(404)]);
+    let output = dir.run(
+        &["download", "--day", "day02", "--base-url", &root],
+        &[("AOC_COOKIE", "s3ss10n")],
+    );
+    assert_eq!(output.status.code(), Some(12));
+    assert!(stderr(&output).contains("404 Not Found"));
+    assert!(!dir.path("data/day02.txt").exists());
+    server.join().unwrap();
+}
+
+#[test]
+fn test_server_error() {
+    let dir = WorkDir::new("cli-server-error");
+    let (root, server) = stub::serve(vec![Reply::status(500)]);
+    let output = dir.run(
+        &[
+            "download",
+            "--day",
+            "day03",
+            "--base-url",
+            &root,
+            "--retries",
+            "0",
+        ],
+        &[("AOC_COOKIE", "s3ss10n")],
+    );
+    assert_eq!(output.status.code(), Some(13));
+    assert!(stderr(&output).contains("500 Internal Server Error"));
+    assert!(!dir.path("data/day03.txt").exists());
+    server.join().unwrap();
+}
+
+#[test]
+fn test_locked_day_and_missing_cookie() {
+    let dir = WorkDir::new("cli-refused");
+    let locked = dir.run(
+        &["download", "--day", "day01", "--year", "3000"],
+        &[("AOC_COOKIE", "s3ss10n")],
+    );
+    assert_eq!(locked.status.code(), Some(11));
+    assert!(stderr(&locked).contains("day 1 is still locked"));
+
+    let no_cookie = dir.run(&["download", "--day", "day01"], &[]);
+    assert_eq!(no_cookie.status.code(), Some(10));
+}
+
+#[test]
+fn test_submit_outcome_exit_code() {
+    let dir = WorkDir::new("cli-submit");
+    let (root, server) = stub::serve(vec![Reply::ok(stub::page(
+        "That's not the right answer; your answer is too high.",
+    ))]);
+    let output = dir.run(
+        &[
+            "submit",
+            "--day",
+            "day04",
+            "--part",
+            "2",
+            "--answer",
+            "99",
+            "--base-url",
+            &root,
+        ],
+        &[("AOC_COOKIE", "s3ss10n")],
+    );
+    assert_eq!(output.status.code(), Some(4));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("too high"));
+    server.join().unwrap();
+}
+
+#[test]
+fn test_downloads_a_range_of_days() {
+    let dir = WorkDir::new("cli-range");
+    fs::create_dir_all(dir.path("data")).unwrap();
+    fs::write(dir.path("data/day01.txt"), "old").unwrap();
+    let (root, server) = stub::serve_paths(vec![
+        ("/2025/day/2/input", Reply::ok("two")),
+        ("/2025/day/3/input", Reply::status(404)),
+        ("/2025/day/4/input", Reply::ok("four")),
+    ]);
+    let output = dir.run(
+        &[
+            "download",
+            "--day",
+            "day01..day03,day04",
+            "--base-url",
+            &root,
+            "--jobs",
+            "3",
+        ],
+        &[("AOC_COOKIE", "s3ss10n"), ("AOC_MIN_INTERVAL", "0")],
+    );
+    assert_eq!(output.status.code(), Some(12));
+    server.join().unwrap();
+    assert_eq!(
+        fs::read_to_string(dir.path("data/day01.txt")).unwrap(),
+        "old"
+    );
+    assert_eq!(
+        fs::read_to_string(dir.path("data/day02.txt")).unwrap(),
+        "two"
+    );
+    assert!(!dir.path("data/day03.txt").exists());
+    assert_eq!(
+        fs::read_to_string(dir.path("data/day04.txt")).unwrap(),
+        "four"
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("  1  skipped     already there\n"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("  3  failed      "), "{}", stdout);
+    assert!(
+        stdout.contains("2 downloaded, 1 skipped, 0 locked, 1 failed"),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_leaderboard_is_cached() {
+    let dir = WorkDir::new("cli-leaderboard");
+    let json = r#"{"event": "2025", "owner_id": 7, "members": {"7": {"id": 7, "name": "Ada",
+        "stars": 1, "local_score": 1, "global_score": 0, "last_star_ts": 1764565260,
+        "completion_day_level": {"1": {"1": {"get_star_ts": 1764565260, "star_index": 0}}}}}}"#;
+    let (root, server) = stub::serve(vec![Reply::ok(json)]);
+    let output = dir.run(
+        &["leaderboard", "--id", "7", "--base-url", &root],
+        &[("AOC_COOKIE", "s3ss10n")],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let request = &server.join().unwrap()[0];
+    assert!(request.starts_with("GET /2025/leaderboard/private/view/7.json HTTP/1.1\r\n"));
+    assert_eq!(
+        fs::read_to_string(dir.path("data/leaderboard-7.json")).unwrap(),
+        json
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("   1      1      1  Ada\n"), "{}", stdout);
+    assert!(
+        stdout.contains("  1         1m 0s             -             -\n"),
+        "{}",
+        stdout
+    );
+
+    // within 15 minutes, and without a cookie, it is read from the cache
+    let cached = dir.run(&["leaderboard", "--id", "7", "--base-url", &root], &[]);
+    assert!(cached.status.success(), "{}", stderr(&cached));
+    assert!(String::from_utf8_lossy(&cached.stdout).contains("Ada"));
+
+    let offline = dir.run(&["leaderboard", "--id", "8", "--offline"], &[]);
+    assert_eq!(offline.status.code(), Some(14));
+}
+
+#[test]
+fn test_cookie_set_and_check() {
+    let dir = WorkDir::new("cli-cookie");
+    let set = run_with_stdin(&dir.0, &["cookie", "set"], &[], "session=53616c7465\n");
+    assert!(set.status.success(), "{}", stderr(&set));
+    let stored = dir.path("config/cookie");
+    assert_eq!(fs::read_to_string(&stored).unwrap(), "53616c7465\n");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&stored).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    let (root, server) = stub::serve(vec![
+        Reply::ok(
+            r#"<header><div class="user">Ada <span class="star-count">2*</span></div></header>"#,
+        ),
+        Reply::ok("<header><a href=\"/2025/auth/login\">[Log In]</a></header>"),
+    ]);
+    let check = dir.run(
+        &[
+            "cookie",
+            "check",
+            "--base-url",
+            &root,
+            "--min-interval",
+            "0",
+        ],
+        &[],
+    );
+    assert!(check.status.success(), "{}", stderr(&check));
+    assert!(String::from_utf8_lossy(&check.stdout).contains("logged in as Ada"));
+    let logged_out = dir.run(
+        &[
+            "cookie",
+            "check",
+            "--base-url",
+            &root,
+            "--min-interval",
+            "0",
+        ],
+        &[],
+    );
+    assert_eq!(logged_out.status.code(), Some(17));
+    let requests = server.join().unwrap();
+    assert!(requests[0].starts_with("GET /2025 HTTP/1.1\r\n"));
+    assert!(requests[0].contains("cookie: session=53616c7465\r\n"));
+
+    let malformed = dir.run(&["cookie", "set", "not a cookie"], &[]);
+    assert_eq!(malformed.status.code(), Some(17));
+}
+
+#[test]
+fn test_expired_cookie() {
+    let dir = WorkDir::new("cli-expired");
+    let (root, server) = stub::serve(vec![Reply {
+        status: 400,
+        body: "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n".into(),
+    }]);
+    let output = dir.run(
+        &["download", "--day", "day05", "--base-url", &root],
+        &[("AOC_COOKIE", "3xp1r3d")],
+    );
+    assert_eq!(output.status.code(), Some(17));
+    assert!(stderr(&output).contains("invalid or has expired"));
+    server.join().unwrap();
+}
+
+#[test]
+fn test_guess_history() {
+    let dir = WorkDir::new("cli-history");
+    let (root, server) = stub::serve(vec![
+        Reply::ok(stub::page(
+            "That's not the right answer; your answer is too low.",
+        )),
+        Reply::ok(stub::page(
+            "That's not the right answer; your answer is too low.",
+        )),
+    ]);
+    let submit = |answer: &str, extra: &[&str]| {
+        let mut args = vec![
+            "submit", "--day", "day05", "--part", "1", "--answer", answer,
+        ];
+        args.extend_from_slice(&["--base-url", &root, "--min-interval", "0"]);
+        args.extend_from_slice(extra);
+        dir.run(&args, &[("AOC_COOKIE", "s3ss10n")])
+    };
+    assert_eq!(submit("548", &[]).status.code(), Some(5));
+
+    // known without asking the server
+    let again = submit("548", &[]);
+    assert_eq!(again.status.code(), Some(5));
+    assert!(String::from_utf8_lossy(&again.stdout).contains("not submitted"));
+    assert_eq!(submit("500", &[]).status.code(), Some(5));
+
+    assert_eq!(submit("500", &["--force"]).status.code(), Some(5));
+    assert_eq!(server.join().unwrap().len(), 2);
+
+    let history = dir.run(&["history", "--day", "day05", "--part", "1"], &[]);
+    assert!(history.status.success(), "{}", stderr(&history));
+    let stdout = String::from_utf8_lossy(&history.stdout);
+    assert_eq!(stdout.matches("too-low").count(), 2, "{}", stdout);
+    assert!(stdout.contains("between 548 and ?"), "{}", stdout);
+
+    // the guesses of each year are kept apart
+    assert!(dir.path("data/day05-2025.guesses").exists());
+    let other_year = dir.run(&["history", "--day", "day05", "--year", "2024"], &[]);
+    let stdout = String::from_utf8_lossy(&other_year.stdout);
+    assert!(!stdout.contains("548"), "{}", stdout);
+}
+
+#[test]
+fn test_generates_example_tests() {
+    let dir = WorkDir::new("cli-examples");
+    fs::create_dir_all(dir.path("data")).unwrap();
+    fs::create_dir_all(dir.path("day03")).unwrap();
+    fs::write(
+        dir.path("data/day03.html"),
+        r#"<article class="day-desc"><h2>--- Day 3: Lobby ---</h2><pre><code>987
+811
+</code></pre><p>The total is <code><em>357</em></code>.</p></article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><pre><code>1</code></pre>
+<pre><code>2</code></pre><p>Now it is <code><em>42</em></code>.</p></article>"#,
+    )
+    .unwrap();
+    let output = dir.run(&["examples", "--day", "day03"], &[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let tests = fs::read_to_string(dir.path("day03/tests/examples.rs")).unwrap();
+    assert!(tests.contains("const EXAMPLE_1: &str = r#\"987\n811\n\"#;"));
+    assert!(tests.contains("assert_eq!(Day03.part1(EXAMPLE_1).unwrap(), \"357\");"));
+    assert!(
+        tests.contains("#[ignore = \"check the expected answer\"]\nfn test_example_3_part2() {")
+    );
+
+    // hand edits are kept
+    fs::write(dir.path("day03/tests/examples.rs"), "edited").unwrap();
+    dir.run(&["examples", "--day", "day03"], &[]);
+    assert_eq!(
+        fs::read_to_string(dir.path("day03/tests/examples.rs")).unwrap(),
+        "edited"
+    );
+}
+
+#[test]
+fn test_end_to_end_with_mock_server() {
+    let dir = WorkDir::new("cli-mock");
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("../mock-aoc/fixtures");
+    let mut config = mock_aoc::Config::new(fixtures, "m0ck");
+    // an hour into day 1, so day 2 is still locked on the server
+    config.start = Some(unlock_time(2025, 1) + Duration::from_secs(3_600));
+    let server = mock_aoc::serve(config, 0).unwrap();
+    let root = server.url();
+    let run = |args: &[&str]| {
+        let mut args = args.to_vec();
+        args.extend_from_slice(&["--base-url", &root, "--min-interval", "0"]);
+        dir.run(&args, &[("AOC_COOKIE", "m0ck")])
+    };
+
+    let check = run(&["cookie", "check"]);
+    assert!(check.status.success(), "{}", stderr(&check));
+    assert!(String::from_utf8_lossy(&check.stdout).contains("logged in as mock user"));
+
+    let download = run(&["download", "--day", "day01..day02"]);
+    assert_eq!(download.status.code(), Some(12), "{}", stderr(&download));
+    assert_eq!(
+        fs::read_to_string(dir.path("data/day01.txt")).unwrap(),
+        "2\n7\n6\n1\n"
+    );
+    assert!(!dir.path("data/day02.txt").exists());
+
+    let puzzle = run(&["puzzle", "--day", "day01"]);
+    assert!(puzzle.status.success(), "{}", stderr(&puzzle));
+    let markdown = fs::read_to_string(dir.path("data/day01.md")).unwrap();
+    assert!(markdown.contains("Stacking Crates"));
+    assert!(!markdown.contains("Part Two"));
+
+    let submit = |part: &str, answer: &str| {
+        run(&[
+            "submit", "--day", "day01", "--part", part, "--answer", answer,
+        ])
+    };
+    assert_eq!(submit("1", "10").status.code(), Some(5));
+    // wrong answers make the next one wait
+    assert_eq!(submit("1", "16").status.code(), Some(6));
+    server.mock().clock().advance(Duration::from_secs(60));
+    let correct = submit("1", "16");
+    assert_eq!(correct.status.code(), Some(0), "{}", stderr(&correct));
+    let markdown = fs::read_to_string(dir.path("data/day01.md")).unwrap();
+    assert!(markdown.contains("Part Two"), "{}", markdown);
+    assert_eq!(
+        fs::read_to_string(dir.path("data/answers.toml")).unwrap(),
+        "[day01.default]\npart1 = \"16\"\n"
+    );
+    assert_eq!(submit("2", "84").status.code(), Some(0));
+    // the history already knows it is right, the server only if forced
+    let solved = submit("2", "84");
+    assert_eq!(solved.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&solved.stdout).contains("not submitted"));
+    let forced = run(&[
+        "submit", "--day", "day01", "--part", "2", "--answer", "84", "--force",
+    ]);
+    assert_eq!(forced.status.code(), Some(7));
+
+    let leaderboard = run(&["leaderboard", "--id", "4242"]);
+    assert!(leaderboard.status.success(), "{}", stderr(&leaderboard));
+    assert!(String::from_utf8_lossy(&leaderboard.stdout).contains("mock user"));
+
+    let expired = dir.run(
+        &[
+            "download",
+            "--day",
+            "day01",
+            "--force",
+            "--base-url",
+            &root,
+            "--min-interval",
+            "0",
+        ],
+        &[("AOC_COOKIE", "stale")],
+    );
+    assert_eq!(expired.status.code(), Some(17));
+}
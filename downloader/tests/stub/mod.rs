@@ -0,0 +1,109 @@
+//! A local HTTP server standing in for AOC, for testing the downloader.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::thread::{self, JoinHandle};
+
+#[derive(Clone, Debug)]
+pub struct Reply {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Reply {
+    pub fn ok(body: impl Into<String>) -> Self {
+        Self {
+            status: 200,
+            body: body.into(),
+        }
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self {
+            status,
+            body: String::new(),
+        }
+    }
+}
+
+/// A page with `message` where AOC puts it.
+pub fn page(message: &str) -> String {
+    format!(
+        "<html><body><main>\n<article><p>{}</p></article>\n</main></body></html>",
+        message
+    )
+}
+
+/// A path in the temporary directory unique to this process and `name`.
+pub fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("downloader-{}-{}", std::process::id(), name))
+}
+
+/// Serves one request per reply, in order, on a fresh port. Returns its URL
+/// (without a year) and a handle yielding the raw requests it got.
+pub fn serve(replies: Vec<Reply>) -> (String, JoinHandle<Vec<String>>) {
+    let count = replies.len();
+    let mut replies = replies.into_iter();
+    serve_with(count, move |_| replies.next().unwrap())
+}
+
+/// Like `serve`, but answers each request with the reply for its path (404
+/// for unknown ones), whatever order they come in.
+pub fn serve_paths(replies: Vec<(&str, Reply)>) -> (String, JoinHandle<Vec<String>>) {
+    let count = replies.len();
+    let mut replies = replies
+        .into_iter()
+        .map(|(path, reply)| (path.to_string(), reply))
+        .collect::<HashMap<_, _>>();
+    serve_with(count, move |path| {
+        replies.remove(path).unwrap_or_else(|| Reply::status(404))
+    })
+}
+
+// serves `count` requests, answering each with what `reply` gives for its path
+fn serve_with(
+    count: usize,
+    mut reply: impl FnMut(&str) -> Reply + Send + 'static,
+) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let root = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for _ in 0..count {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+            }
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+            let path = request.split(' ').nth(1).unwrap_or("");
+            let reply = reply(path);
+            let response = format!(
+                "HTTP/1.1 {} Stand-in\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                reply.status,
+                reply.body.len(),
+                reply.body
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            requests.push(request);
+        }
+        requests
+    });
+    (root, handle)
+}
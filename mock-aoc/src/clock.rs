@@ -0,0 +1,122 @@
+//! The server's notion of now, which days unlock by: real time shifted to
+//! start wherever the tests or demo need it.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+pub struct Clock {
+    // the fake time at the real instant
+    origin: Mutex<(SystemTime, Instant)>,
+}
+
+impl Clock {
+    /// A clock that starts at `start`, or at the real time if `None`.
+    pub fn new(start: Option<SystemTime>) -> Self {
+        Self {
+            origin: Mutex::new((start.unwrap_or_else(SystemTime::now), Instant::now())),
+        }
+    }
+
+    pub fn now(&self) -> SystemTime {
+        let (fake, real) = *self.origin.lock().unwrap();
+        fake + real.elapsed()
+    }
+
+    pub fn set(&self, now: SystemTime) {
+        *self.origin.lock().unwrap() = (now, Instant::now());
+    }
+
+    pub fn advance(&self, by: Duration) {
+        let mut origin = self.origin.lock().unwrap();
+        origin.0 += by;
+    }
+}
+
+/// When the puzzle of `day` unlocks: midnight US Eastern, which is always
+/// 05:00 UTC in December.
+pub fn unlock_time(year: i32, day: u32) -> SystemTime {
+    at(year as i64, 12, day as i64, 5 * 3_600)
+}
+
+/// Parses a time given as seconds since the epoch, as `2025-12-03` or as
+/// `2025-12-03T05:00:00Z`, all in UTC.
+pub fn parse_time(text: &str) -> Option<SystemTime> {
+    if let Ok(secs) = text.parse::<u64>() {
+        return Some(UNIX_EPOCH + Duration::from_secs(secs));
+    }
+    let (date, time) = match text.split_once('T') {
+        Some((date, time)) => (date, time.strip_suffix('Z').unwrap_or(time)),
+        None => (text, "00:00:00"),
+    };
+    let date = date
+        .split('-')
+        .map(|n| n.parse::<i64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let time = time
+        .split(':')
+        .map(|n| n.parse::<i64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    match (&date[..], &time[..]) {
+        (&[y, m, d], &[h, min, s]) if (1..=12).contains(&m) && (1..=31).contains(&d) => {
+            Some(at(y, m, d, h * 3_600 + min * 60 + s))
+        }
+        _ => None,
+    }
+}
+
+/// Shows a duration as `1h 2m 3s`, leaving out leading zero units.
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match (secs / 3600, secs / 60 % 60, secs % 60) {
+        (0, 0, s) => format!("{}s", s),
+        (0, m, s) => format!("{}m {}s", m, s),
+        (h, m, s) => format!("{}h {}m {}s", h, m, s),
+    }
+}
+
+fn at(year: i64, month: i64, day: i64, secs: i64) -> SystemTime {
+    let secs = days_from_civil(year, month, day) * 86_400 + secs;
+    UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)
+}
+
+// days since 1970-01-01 of a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(time: SystemTime) -> u64 {
+        time.duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(secs(unlock_time(2025, 1)), 1_764_565_200);
+        assert_eq!(
+            parse_time("2025-12-01T05:00:00Z"),
+            Some(unlock_time(2025, 1))
+        );
+        assert_eq!(secs(parse_time("2025-12-01").unwrap()), 1_764_547_200);
+        assert_eq!(secs(parse_time("1764565200").unwrap()), 1_764_565_200);
+        assert_eq!(parse_time("2025-13-01"), None);
+        assert_eq!(parse_time("yesterday"), None);
+    }
+
+    #[test]
+    fn test_clock() {
+        let clock = Clock::new(Some(unlock_time(2025, 1)));
+        clock.advance(Duration::from_secs(60));
+        let now = secs(clock.now());
+        assert!((1_764_565_260..1_764_565_262).contains(&now));
+        clock.set(unlock_time(2025, 2));
+        assert!(secs(clock.now()) >= 1_764_651_600);
+    }
+}
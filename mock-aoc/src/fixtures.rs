@@ -0,0 +1,204 @@
+//! The puzzles served, read from a directory laid out as
+//!
+//! ```text
+//! fixtures/2025/day01/input.txt     the input of every user
+//! fixtures/2025/day01/part1.html    inside of the first <article>
+//! fixtures/2025/day01/part2.html    shown once part 1 is solved
+//! fixtures/2025/day01/answers.txt   "part answer [verdict]" per line
+//! fixtures/2025/leaderboard/ID.json a private leaderboard
+//! ```
+//!
+//! Files are read on every request, so fixtures can be edited while the
+//! server runs.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// What the server replies to an answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "rate-limited" => Ok(Verdict::RateLimited),
+            _ => Err(format!("unknown verdict {:?}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::RateLimited => "rate-limited",
+        })
+    }
+}
+
+/// The lines of `answers.txt`: a part and its answer is the right one, with
+/// a verdict it is what submitting that answer gets.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    correct: Vec<(u32, String)>,
+    canned: Vec<(u32, String, Verdict)>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = Answers::default();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let part = fields[0]
+                .parse()
+                .ok()
+                .filter(|p| (1..=2).contains(p))
+                .ok_or_else(|| format!("line {}: invalid part {:?}", n + 1, fields[0]))?;
+            match fields[1..] {
+                [answer] => answers.correct.push((part, answer.to_string())),
+                [answer, verdict] => {
+                    let verdict = verdict
+                        .parse()
+                        .map_err(|e| format!("line {}: {}", n + 1, e))?;
+                    answers.canned.push((part, answer.to_string(), verdict));
+                }
+                _ => {
+                    return Err(format!(
+                        "line {}: expected \"part answer [verdict]\"",
+                        n + 1
+                    ));
+                }
+            }
+        }
+        Ok(answers)
+    }
+
+    pub fn correct(&self, part: u32) -> Option<&str> {
+        self.correct
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, answer)| answer.as_str())
+    }
+
+    /// The verdict on `answer`: the canned one if there is, otherwise how it
+    /// compares to the right answer, numerically when both are numbers.
+    pub fn judge(&self, part: u32, answer: &str) -> Verdict {
+        if let Some((_, _, verdict)) = self
+            .canned
+            .iter()
+            .find(|(p, a, _)| *p == part && a == answer)
+        {
+            return *verdict;
+        }
+        let Some(correct) = self.correct(part) else {
+            return Verdict::Incorrect;
+        };
+        if answer == correct {
+            return Verdict::Correct;
+        }
+        match (answer.parse::<i128>(), correct.parse::<i128>()) {
+            (Ok(a), Ok(c)) if a > c => Verdict::TooHigh,
+            (Ok(a), Ok(c)) if a < c => Verdict::TooLow,
+            _ => Verdict::Incorrect,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Fixtures {
+    root: PathBuf,
+}
+
+impl Fixtures {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn has_year(&self, year: i32) -> bool {
+        self.root.join(year.to_string()).is_dir()
+    }
+
+    fn day_dir(&self, year: i32, day: u32) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("day{:02}", day))
+    }
+
+    pub fn has_day(&self, year: i32, day: u32) -> bool {
+        self.day_dir(year, day).is_dir()
+    }
+
+    pub fn input(&self, year: i32, day: u32) -> Option<String> {
+        fs::read_to_string(self.day_dir(year, day).join("input.txt")).ok()
+    }
+
+    pub fn part(&self, year: i32, day: u32, part: u32) -> Option<String> {
+        fs::read_to_string(self.day_dir(year, day).join(format!("part{}.html", part))).ok()
+    }
+
+    /// The answers of a day, none if it has no `answers.txt`.
+    pub fn answers(&self, year: i32, day: u32) -> Result<Answers, String> {
+        let path = self.day_dir(year, day).join("answers.txt");
+        match fs::read_to_string(&path) {
+            Ok(text) => Answers::parse(&text).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(_) => Ok(Answers::default()),
+        }
+    }
+
+    pub fn leaderboard(&self, year: i32, id: &str) -> Option<String> {
+        let path = self
+            .root
+            .join(year.to_string())
+            .join("leaderboard")
+            .join(format!("{}.json", id));
+        fs::read_to_string(path).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_judge_answers() {
+        let answers = Answers::parse(
+            "# part answer [verdict]\n1 12\n2 abc\n1 11 incorrect  # not too low\n\n1 5 rate-limited\n",
+        )
+        .unwrap();
+        assert_eq!(answers.correct(1), Some("12"));
+        assert_eq!(answers.judge(1, "12"), Verdict::Correct);
+        assert_eq!(answers.judge(1, "13"), Verdict::TooHigh);
+        assert_eq!(answers.judge(1, "10"), Verdict::TooLow);
+        assert_eq!(answers.judge(1, "11"), Verdict::Incorrect);
+        assert_eq!(answers.judge(1, "5"), Verdict::RateLimited);
+        assert_eq!(answers.judge(2, "abd"), Verdict::Incorrect);
+        assert_eq!(answers.judge(2, "abc"), Verdict::Correct);
+        assert!(Answers::parse("3 1").is_err());
+        assert!(Answers::parse("1 1 maybe").is_err());
+    }
+}
@@ -0,0 +1,489 @@
+//! An offline stand-in for adventofcode.com, serving puzzles from a
+//! directory of fixtures so that the downloader can be tried and tested
+//! end to end without a network, an account or waiting for December.
+//!
+//! It answers the same URLs as the real site, only to the configured
+//! session cookie, keeps every day locked until its unlock time on a fake
+//! clock, and makes wrong answers wait before the next one.
+
+pub mod clock;
+pub mod fixtures;
+pub mod http;
+
+use clock::{Clock, format_duration, unlock_time};
+use fixtures::{Fixtures, Verdict};
+use http::{Request, Response};
+use std::collections::HashMap;
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub fixtures: PathBuf,
+    /// The value of the `session` cookie that is logged in.
+    pub session: String,
+    /// The name shown to the logged in user.
+    pub user: String,
+    /// Where the fake clock starts, the real time if `None`.
+    pub start: Option<SystemTime>,
+    /// How long a wrong answer makes the next one wait.
+    pub answer_wait: Duration,
+    /// Print every request to stderr.
+    pub log: bool,
+}
+
+impl Config {
+    pub fn new(fixtures: impl Into<PathBuf>, session: impl Into<String>) -> Self {
+        Self {
+            fixtures: fixtures.into(),
+            session: session.into(),
+            user: "mock user".to_string(),
+            start: None,
+            answer_wait: Duration::from_secs(60),
+            log: false,
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    // the right answers given, by (year, day, part)
+    solved: HashMap<(i32, u32, u32), String>,
+    // no answers are judged before this
+    wait_until: Option<SystemTime>,
+}
+
+/// The site itself: turns requests into responses.
+pub struct MockAoc {
+    config: Config,
+    fixtures: Fixtures,
+    clock: Clock,
+    state: Mutex<State>,
+}
+
+const LOGGED_OUT: &str = "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+
+const TOO_EARLY: &str = "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n";
+
+impl MockAoc {
+    pub fn new(config: Config) -> Self {
+        Self {
+            fixtures: Fixtures::new(&config.fixtures),
+            clock: Clock::new(config.start),
+            config,
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
+    pub fn handle(&self, request: &Request) -> Response {
+        let path = request.path.split('?').next().unwrap_or_default();
+        let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+        let Some(year) = segments[0]
+            .parse()
+            .ok()
+            .filter(|y| self.fixtures.has_year(*y))
+        else {
+            return Response::html(404, self.page("404 Not Found", false, "Not found."));
+        };
+        let logged_in = request.cookie("session") == Some(self.config.session.as_str());
+        match (request.method.as_str(), &segments[1..]) {
+            ("GET", []) => self.calendar(year, logged_in),
+            ("GET", ["leaderboard", "private", "view", file]) => match file.strip_suffix(".json") {
+                Some(_) if !logged_in => Response::text(400, "Please log in.\n"),
+                Some(id) => match self.fixtures.leaderboard(year, id) {
+                    Some(json) => Response::json(json),
+                    None => Response::text(404, "404 Not Found\n"),
+                },
+                None => Response::text(404, "404 Not Found\n"),
+            },
+            (method, ["day", day, rest @ ..]) => {
+                let Some(day) = day.parse().ok().filter(|d| self.fixtures.has_day(year, *d)) else {
+                    return Response::text(404, "404 Not Found\n");
+                };
+                if self.clock.now() < unlock_time(year, day) {
+                    return Response::text(404, TOO_EARLY);
+                }
+                match (method, rest) {
+                    ("GET", []) => self.puzzle(year, day, logged_in),
+                    ("GET", ["input"]) if !logged_in => Response::text(400, LOGGED_OUT),
+                    ("GET", ["input"]) => match self.fixtures.input(year, day) {
+                        Some(input) => Response::text(200, input),
+                        None => Response::text(404, "404 Not Found\n"),
+                    },
+                    ("POST", ["answer"]) if !logged_in => Response::text(400, "Please log in.\n"),
+                    ("POST", ["answer"]) => self.answer(year, day, request),
+                    _ => Response::text(404, "404 Not Found\n"),
+                }
+            }
+            _ => Response::text(404, "404 Not Found\n"),
+        }
+    }
+
+    fn page(&self, title: &str, logged_in: bool, main: &str) -> String {
+        let user = if logged_in {
+            let stars = self.state.lock().unwrap().solved.len();
+            format!(
+                r#"<div class="user">{} <span class="star-count">{}*</span></div>"#,
+                self.config.user, stars
+            )
+        } else {
+            r#"<div><a href="/auth/login">[Log In]</a></div>"#.to_string()
+        };
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en-us\">\n<head>\n<meta charset=\"utf-8\"/>\n<title>{}</title>\n</head>\n<body>\n<header><h1 class=\"title-global\"><a href=\"/\">Advent of Code</a></h1>{}</header>\n<main>\n{}\n</main>\n</body>\n</html>\n",
+            title, user, main
+        )
+    }
+
+    fn calendar(&self, year: i32, logged_in: bool) -> Response {
+        let now = self.clock.now();
+        let days = (1..=25)
+            .filter(|day| self.fixtures.has_day(year, *day) && unlock_time(year, *day) <= now)
+            .map(|day| format!(r#"<a href="/{0}/day/{1}">Day {1}</a>"#, year, day))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let main = format!("<pre class=\"calendar\">\n{}\n</pre>", days);
+        Response::html(
+            200,
+            self.page(&format!("Advent of Code {}", year), logged_in, &main),
+        )
+    }
+
+    fn puzzle(&self, year: i32, day: u32, logged_in: bool) -> Response {
+        let Some(part1) = self.fixtures.part(year, day, 1) else {
+            return Response::text(404, "404 Not Found\n");
+        };
+        let state = self.state.lock().unwrap();
+        let answer = |part| {
+            state
+                .solved
+                .get(&(year, day, part))
+                .filter(|_| logged_in)
+                .map(|a| format!("<p>Your puzzle answer was <code>{}</code>.</p>", a))
+        };
+        let mut main = format!("<article class=\"day-desc\">{}</article>\n", part1);
+        if let Some(answer1) = answer(1) {
+            main += &answer1;
+            if let Some(part2) = self.fixtures.part(year, day, 2) {
+                main += &format!("<article class=\"day-desc\">{}</article>\n", part2);
+            }
+            main += &answer(2).unwrap_or_default();
+        }
+        drop(state);
+        let title = format!("Day {} - Advent of Code {}", day, year);
+        Response::html(200, self.page(&title, logged_in, main.trim_end()))
+    }
+
+    fn answer(&self, year: i32, day: u32, request: &Request) -> Response {
+        let form = request.form();
+        let (Some(part), Some(answer)) = (
+            form.get("level")
+                .and_then(|l| l.parse().ok())
+                .filter(|p| (1..=2).contains(p)),
+            form.get("answer"),
+        ) else {
+            return Response::text(400, "Bad request.\n");
+        };
+        let answers = match self.fixtures.answers(year, day) {
+            Ok(answers) => answers,
+            Err(e) => return Response::text(500, format!("{}\n", e)),
+        };
+        let now = self.clock.now();
+        let mut state = self.state.lock().unwrap();
+        let back = format!(
+            r#"[<a href="/{}/day/{}">Return to Day {}</a>]"#,
+            year, day, day
+        );
+        let message = if let Some(left) = state.wait_until.and_then(|t| t.duration_since(now).ok())
+        {
+            too_recently(left, &back)
+        } else if state.solved.contains_key(&(year, day, part))
+            || (part == 2 && !state.solved.contains_key(&(year, day, 1)))
+        {
+            format!(
+                "You don't seem to be solving the right level.  Did you already complete it? {}",
+                back
+            )
+        } else {
+            let verdict = answers.judge(part, answer);
+            let wrong = |hint: &str| {
+                format!(
+                    "That's not the right answer{}.  If you're stuck, make sure you're using the full input data.  Please wait {} before trying again. {}",
+                    hint,
+                    wait_text(self.config.answer_wait),
+                    back
+                )
+            };
+            let message = match verdict {
+                Verdict::Correct => {
+                    state.solved.insert((year, day, part), answer.clone());
+                    format!(
+                        "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to finishing. [<a href=\"/{}/day/{}#part2\">Continue</a>]",
+                        year, day
+                    )
+                }
+                Verdict::RateLimited => too_recently(self.config.answer_wait, &back),
+                Verdict::Incorrect => wrong(""),
+                Verdict::TooHigh => wrong("; your answer is too high"),
+                Verdict::TooLow => wrong("; your answer is too low"),
+            };
+            if matches!(
+                verdict,
+                Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+            ) {
+                state.wait_until = Some(now + self.config.answer_wait);
+            }
+            message
+        };
+        drop(state);
+        let main = format!("<article><p>{}</p></article>", message);
+        let title = format!("Day {} - Advent of Code {}", day, year);
+        Response::html(200, self.page(&title, true, &main))
+    }
+}
+
+fn too_recently(left: Duration, back: &str) -> String {
+    // rounded up, as the site does
+    let left = Duration::from_secs(left.as_secs() + u64::from(left.subsec_nanos() > 0));
+    format!(
+        "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {} left to wait. {}",
+        format_duration(left),
+        back
+    )
+}
+
+fn wait_text(wait: Duration) -> String {
+    match wait.as_secs() {
+        60 => "one minute".to_string(),
+        secs if secs % 60 == 0 => format!("{} minutes", secs / 60),
+        _ => format_duration(wait),
+    }
+}
+
+/// A running `MockAoc`, stopped when dropped.
+pub struct Server {
+    addr: SocketAddr,
+    mock: Arc<MockAoc>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// Serves `config` on `127.0.0.1:port`, a free port if `port` is 0.
+pub fn serve(config: Config, port: u16) -> io::Result<Server> {
+    let listener = TcpListener::bind(("127.0.0.1", port))?;
+    let addr = listener.local_addr()?;
+    let log = config.log;
+    let mock = Arc::new(MockAoc::new(config));
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread = {
+        let mock = Arc::clone(&mock);
+        let stop = Arc::clone(&stop);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::SeqCst) {
+                    break;
+                }
+                let Ok(mut stream) = stream else { continue };
+                let mock = Arc::clone(&mock);
+                thread::spawn(move || {
+                    let Ok(request) = Request::read(&mut stream) else {
+                        return;
+                    };
+                    let response = mock.handle(&request);
+                    if log {
+                        eprintln!("{} {} {}", response.status, request.method, request.path);
+                    }
+                    let _ = response.write_to(&mut stream);
+                });
+            }
+        })
+    };
+    Ok(Server {
+        addr,
+        mock,
+        stop,
+        thread: Some(thread),
+    })
+}
+
+impl Server {
+    /// The root of the site, to which the downloader adds `/{year}`.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    pub fn mock(&self) -> &MockAoc {
+        &self.mock
+    }
+
+    /// Serves until the process is killed.
+    pub fn wait(mut self) {
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // wakes up the accept loop to see the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const SESSION: &str = "c00k1e";
+
+    fn fixtures(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("mock-aoc-{}-{}", name, std::process::id()));
+        let day = root.join("2025/day01");
+        fs::create_dir_all(&day).unwrap();
+        fs::create_dir_all(root.join("2025/day02")).unwrap();
+        fs::create_dir_all(root.join("2025/leaderboard")).unwrap();
+        fs::write(day.join("input.txt"), "3\n4\n5\n").unwrap();
+        fs::write(day.join("part1.html"), "<h2>--- Day 1: Sums ---</h2>").unwrap();
+        fs::write(
+            day.join("part2.html"),
+            "<h2 id=\"part2\">--- Part Two ---</h2>",
+        )
+        .unwrap();
+        fs::write(day.join("answers.txt"), "1 12\n2 60\n").unwrap();
+        fs::write(root.join("2025/leaderboard/42.json"), "{}").unwrap();
+        root
+    }
+
+    fn get(path: &str, session: Option<&str>) -> Request {
+        request("GET", path, session, "")
+    }
+
+    fn request(method: &str, path: &str, session: Option<&str>, body: &str) -> Request {
+        let mut text = format!("{} {} HTTP/1.1\r\n", method, path);
+        if let Some(session) = session {
+            text += &format!("Cookie: session={}\r\n", session);
+        }
+        text += &format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+        // parsed from a socket, as the server does
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        io::Write::write_all(&mut client, text.as_bytes()).unwrap();
+        let (mut stream, _) = listener.accept().unwrap();
+        Request::read(&mut stream).unwrap()
+    }
+
+    fn mock(name: &str) -> MockAoc {
+        let mut config = Config::new(fixtures(name), SESSION);
+        config.start = Some(unlock_time(2025, 1));
+        MockAoc::new(config)
+    }
+
+    #[test]
+    fn test_inputs_need_the_session_and_unlock() {
+        let mock = mock("inputs");
+        let input = mock.handle(&get("/2025/day/1/input", Some(SESSION)));
+        assert_eq!((input.status, input.body.as_str()), (200, "3\n4\n5\n"));
+        let logged_out = mock.handle(&get("/2025/day/1/input", Some("stale")));
+        assert_eq!(
+            (logged_out.status, logged_out.body.as_str()),
+            (400, LOGGED_OUT)
+        );
+        assert_eq!(
+            mock.handle(&get("/2025/day/2/input", Some(SESSION))).status,
+            404
+        );
+        assert_eq!(
+            mock.handle(&get("/2025/day/3/input", Some(SESSION))).status,
+            404
+        );
+        assert_eq!(
+            mock.handle(&get("/2024/day/1/input", Some(SESSION))).status,
+            404
+        );
+        mock.clock().set(unlock_time(2025, 2));
+        assert_eq!(
+            mock.handle(&get("/2025/day/2/input", Some(SESSION))).status,
+            404
+        );
+        let calendar = mock.handle(&get("/2025", Some(SESSION))).body;
+        assert!(calendar.contains("Day 2</a>"));
+        assert!(
+            calendar.contains(
+                r#"<div class="user">mock user <span class="star-count">0*</span></div>"#
+            )
+        );
+        assert!(
+            !mock
+                .handle(&get("/2025", None))
+                .body
+                .contains("class=\"user\"")
+        );
+    }
+
+    #[test]
+    fn test_answers_unlock_part_two_and_wait() {
+        let mock = mock("answers");
+        let submit = |part, answer: &str| {
+            let body = format!("level={}&answer={}", part, answer);
+            mock.handle(&request("POST", "/2025/day/1/answer", Some(SESSION), &body))
+                .body
+        };
+        assert!(
+            !mock
+                .handle(&get("/2025/day/1", Some(SESSION)))
+                .body
+                .contains("Part Two")
+        );
+        assert!(submit(2, "60").contains("Did you already complete it?"));
+        assert!(submit(1, "11").contains("your answer is too low"));
+        assert!(submit(1, "12").contains("You have 1m 0s left to wait."));
+        mock.clock().advance(Duration::from_secs(45));
+        assert!(submit(1, "12").contains("You have 15s left to wait."));
+        mock.clock().advance(Duration::from_secs(15));
+        assert!(submit(1, "12").contains("That's the right answer!"));
+        let page = mock.handle(&get("/2025/day/1", Some(SESSION))).body;
+        assert!(page.contains("Your puzzle answer was <code>12</code>."));
+        assert!(page.contains("--- Part Two ---"));
+        assert!(submit(1, "12").contains("Did you already complete it?"));
+        assert!(submit(2, "60").contains("That's the right answer!"));
+        let logged_out = mock.handle(&get("/2025/day/1", None)).body;
+        assert!(!logged_out.contains("Part Two"));
+    }
+
+    #[test]
+    fn test_serves_leaderboards_over_http() {
+        let server = serve(Config::new(fixtures("leaderboard"), SESSION), 0).unwrap();
+        let mut stream = TcpStream::connect(server.addr).unwrap();
+        let request = format!(
+            "GET /2025/leaderboard/private/view/42.json HTTP/1.1\r\nCookie: session={}\r\n\r\n",
+            SESSION
+        );
+        io::Write::write_all(&mut stream, request.as_bytes()).unwrap();
+        let mut response = String::new();
+        io::Read::read_to_string(&mut stream, &mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("\r\n\r\n{}"));
+        assert_eq!(
+            server
+                .mock()
+                .handle(&get("/2025/leaderboard/private/view/7.json", Some(SESSION)))
+                .status,
+            404
+        );
+    }
+}
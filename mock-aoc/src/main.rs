@@ -0,0 +1,61 @@
+use clap::Parser;
+use mock_aoc::Config;
+use mock_aoc::clock::parse_time;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, SystemTime};
+
+/// Serves Advent of Code puzzles from a directory of fixtures.
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// Directory with a `YEAR/dayNN/` folder per puzzle
+    #[arg(long, default_value = "mock-aoc/fixtures", env = "MOCK_AOC_FIXTURES")]
+    fixtures: PathBuf,
+    #[arg(long, default_value_t = 8025, env = "MOCK_AOC_PORT")]
+    port: u16,
+    /// The session cookie that is logged in
+    #[arg(long, default_value = "mock-session", env = "MOCK_AOC_SESSION")]
+    session: String,
+    /// Name shown to the logged in user
+    #[arg(long, default_value = "mock user")]
+    user: String,
+    /// Start of the fake clock: 2025-12-03, 2025-12-03T05:00:00Z or seconds
+    /// since the epoch, all UTC [default: now]
+    #[arg(long, value_parser = parse_now)]
+    now: Option<SystemTime>,
+    /// Seconds a wrong answer makes the next one wait
+    #[arg(long, default_value_t = 60)]
+    answer_wait: u64,
+}
+
+fn parse_now(text: &str) -> Result<SystemTime, String> {
+    parse_time(text).ok_or_else(|| format!("invalid time {:?}", text))
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let config = Config {
+        fixtures: args.fixtures,
+        session: args.session,
+        user: args.user,
+        start: args.now,
+        answer_wait: Duration::from_secs(args.answer_wait),
+        log: true,
+    };
+    if !config.fixtures.is_dir() {
+        eprintln!("Error: {} is not a directory", config.fixtures.display());
+        return ExitCode::FAILURE;
+    }
+    match mock_aoc::serve(config, args.port) {
+        Ok(server) => {
+            println!("Serving on {}", server.url());
+            server.wait();
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
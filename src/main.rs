@@ -1,3 +1,300 @@
-fn main() {
-    println!("Dummy main");
-}
\ No newline at end of file
+use clap::{Parser, Subcommand};
+use common::answers::{Answers, Check, DEFAULT_INPUT};
+use common::input::STDIN;
+use common::{ParseError, Solution, Source, input_sets};
+use std::error::Error;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::day01::Day01,
+    &day02::day02::Day02,
+    &day03::day03::Day03,
+    &day04::day04::Day04,
+    &day05::day05::Day05,
+    &day06::day06::Day06,
+    &day07::day07::Day07,
+    &day08::day08::Day08,
+    &day09::day09::Day09,
+    &day10::day10::Day10,
+    &day11::day11::Day11,
+    &day12::day12::Day12,
+];
+
+/// Runs the solutions of AOC 2025 and reports their answers and timings
+#[derive(Debug, Parser)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Day to run (e.g. 3 or day03); all days if absent
+    #[clap(short, long, global = true, value_parser = parse_day)]
+    day: Option<u32>,
+
+    /// Part to run; both parts if absent
+    #[clap(short, long, global = true, value_parser = clap::value_parser!(u32).range(1..=2))]
+    part: Option<u32>,
+
+    /// Input file to use instead of dayNN.txt in the data directory, or - for
+    /// stdin (requires --day)
+    #[clap(short, long, global = true, requires = "day")]
+    input: Option<String>,
+
+    /// Input set to use, from data/dayNN/NAME.txt (default for dayNN.txt), or
+    /// all of them; days without it are skipped
+    #[clap(short = 's', long, global = true, conflicts_with = "input")]
+    input_set: Option<String>,
+}
+
+/// `--input-set` value for every input set of a day.
+const ALL_SETS: &str = "all";
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Checks the answers against those in data/answers.toml, under the name
+    /// of the input set or file (default for dayNN.txt)
+    Verify,
+}
+
+fn parse_day(day: &str) -> Result<u32, String> {
+    let digits = day.strip_prefix("day").unwrap_or(day);
+    match digits.parse::<u32>() {
+        Ok(n) if SOLUTIONS.iter().any(|s| s.day() == n) => Ok(n),
+        _ => Err(format!("no solution for day {}", day)),
+    }
+}
+
+struct Row {
+    day: u32,
+    title: &'static str,
+    input: String,
+    /// 0 for parsing the input, which the parts are timed without.
+    part: u32,
+    /// `None` for the part itself, else the name of a variant of it.
+    variant: Option<&'static str>,
+    outcome: Result<String, String>,
+    elapsed: Duration,
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+// parse errors from the solutions only know their line, so we add the file
+fn describe(e: Box<dyn Error>, path: &str) -> String {
+    match e.downcast::<ParseError>() {
+        Ok(e) => e.in_file(path).to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+// the inputs to run a day on, by the name their answers are kept under
+fn inputs(solution: &dyn Solution, args: &Args) -> Vec<(String, common::Result<Source>)> {
+    match (&args.input, &args.input_set) {
+        (None, Some(set)) => input_sets(solution.day())
+            .into_iter()
+            .filter(|s| set == ALL_SETS || s.name == *set)
+            .map(|s| (s.name, Ok(Source::File(s.path))))
+            .collect(),
+        (explicit, _) => vec![(
+            input_name(explicit.as_deref()),
+            Source::resolve(&solution.input_name(), explicit.as_deref()),
+        )],
+    }
+}
+
+fn run_day(
+    solution: &dyn Solution,
+    input: &str,
+    source: common::Result<Source>,
+    args: &Args,
+) -> Vec<Row> {
+    let row = |part, variant, outcome, elapsed| Row {
+        day: solution.day(),
+        title: solution.title(),
+        input: input.to_owned(),
+        part,
+        variant,
+        outcome,
+        elapsed,
+    };
+    let path = source
+        .as_ref()
+        .map(Source::display_name)
+        .unwrap_or_default();
+    let input = match source.and_then(|s| s.read()) {
+        Ok(input) => input,
+        Err(e) => {
+            let message = format!("cannot load input: {}", e);
+            return vec![row(0, None, Err(message), Duration::ZERO)];
+        }
+    };
+    let (parsed, elapsed) = timed(|| solution.parse(&input));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => return vec![row(0, None, Err(e.in_file(&path).to_string()), elapsed)],
+    };
+    let mut rows = vec![row(0, None, Ok(String::new()), elapsed)];
+    for part in [1, 2] {
+        if args.part.is_some_and(|p| p != part) {
+            continue;
+        }
+        let (outcome, elapsed) = timed(|| match part {
+            1 => Some(parsed.part1()),
+            _ => parsed.part2(),
+        });
+        let Some(outcome) = outcome else {
+            continue;
+        };
+        let outcome = outcome.map_err(|e| describe(e, &path));
+        // the variants of the part, each checked against its answer
+        let variants = solution.variants().into_iter().filter(|v| v.part == part);
+        let variants = variants
+            .map(|variant| {
+                let (answer, elapsed) = timed(|| parsed.variant(variant));
+                let answer = match &outcome {
+                    Ok(expected) if answer.is_ok() => variant.agree(answer, expected),
+                    _ => answer.map_err(|e| describe(e, &path)),
+                };
+                row(part, Some(variant.name), answer, elapsed)
+            })
+            .collect::<Vec<_>>();
+        rows.push(row(part, None, outcome, elapsed));
+        rows.extend(variants);
+    }
+    rows
+}
+
+fn print_table(rows: &[Row]) {
+    println!(
+        "{:>3}  {:<20}  {:<10}  {:>5}  {:<12}  {:<20}  {:>12}",
+        "Day", "Title", "Input", "Part", "Variant", "Answer", "Time"
+    );
+    for r in rows {
+        let answer = match &r.outcome {
+            Ok(answer) => answer.clone(),
+            Err(e) => format!("error: {}", e),
+        };
+        println!(
+            "{:>3}  {:<20}  {:<10}  {:>5}  {:<12}  {:<20}  {:>12}",
+            r.day,
+            r.title,
+            r.input,
+            part_label(r.part),
+            r.variant.unwrap_or("-"),
+            answer,
+            format!("{:.2?}", r.elapsed)
+        );
+    }
+    let total = rows.iter().map(|r| r.elapsed).sum::<Duration>();
+    println!("{:>94}", format!("total {:.2?}", total));
+}
+
+fn part_label(part: u32) -> String {
+    match part {
+        0 => "parse".to_string(),
+        part => part.to_string(),
+    }
+}
+
+// the name the answers of an input are kept under
+fn input_name(explicit: Option<&str>) -> String {
+    match explicit {
+        None => DEFAULT_INPUT.to_owned(),
+        Some(STDIN) => "stdin".to_owned(),
+        Some(path) => Path::new(path).file_stem().map_or_else(
+            || path.to_owned(),
+            |stem| stem.to_string_lossy().into_owned(),
+        ),
+    }
+}
+
+fn print_verification(rows: &[Row], answers: &Answers) -> bool {
+    println!(
+        "{:>3}  {:<20}  {:<10}  {:>5}  {:<12}  {:<20}  {:<20}  Status",
+        "Day", "Title", "Input", "Part", "Variant", "Answer", "Expected"
+    );
+    let mut counts = [0; 4];
+    for r in rows {
+        let (answer, expected, status) = match &r.outcome {
+            // parsing has no answer to check
+            Ok(_) if r.part == 0 => continue,
+            Ok(answer) => {
+                let expected = answers.get(r.day, r.part, &r.input);
+                let check = Check::of(expected.as_deref(), answer);
+                let status = match check {
+                    Check::Pass => 0,
+                    Check::Fail { .. } => 1,
+                    Check::Unknown => 2,
+                };
+                counts[status] += 1;
+                let expected = expected.unwrap_or_else(|| "-".to_string());
+                (answer.clone(), expected, check.to_string())
+            }
+            Err(e) => {
+                counts[3] += 1;
+                (
+                    format!("error: {}", e),
+                    "-".to_string(),
+                    "error".to_string(),
+                )
+            }
+        };
+        println!(
+            "{:>3}  {:<20}  {:<10}  {:>5}  {:<12}  {:<20}  {:<20}  {}",
+            r.day,
+            r.title,
+            r.input,
+            part_label(r.part),
+            r.variant.unwrap_or("-"),
+            answer,
+            expected,
+            status
+        );
+    }
+    println!(
+        "{} pass, {} fail, {} unknown, {} error",
+        counts[0], counts[1], counts[2], counts[3]
+    );
+    counts[1] == 0 && counts[3] == 0
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let rows = SOLUTIONS
+        .iter()
+        .filter(|s| args.day.is_none_or(|d| d == s.day()))
+        .flat_map(|s| {
+            inputs(*s, &args)
+                .into_iter()
+                .flat_map(|(input, source)| run_day(*s, &input, source, &args))
+        })
+        .collect::<Vec<_>>();
+    if let Some(set) = &args.input_set
+        && rows.is_empty()
+    {
+        eprintln!("Error: no input set {} for the days asked", set);
+        return ExitCode::FAILURE;
+    }
+    let ok = match args.command {
+        None => {
+            print_table(&rows);
+            rows.iter().all(|r| r.outcome.is_ok())
+        }
+        Some(Command::Verify) => match Answers::load() {
+            Ok(answers) => print_verification(&rows, &answers),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                false
+            }
+        },
+    };
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -1,9 +1,16 @@
-use {{project-name}}::{{project-name}}::{INPUT, part1, part2};
+use common::{Day, read_input_or};
+use {{project-name}}::{{project-name}}::{EXAMPLE, INPUT, part1, part2, {{project-name | pascal_case}}};
 
 fn main() {
     divan::main()
 }
 
+#[divan::bench]
+fn bench_parse(bencher: divan::Bencher) {
+    let input = read_input_or("{{project-name}}.txt", || EXAMPLE.to_owned());
+    bencher.bench(|| {{project-name | pascal_case}}.parse(divan::black_box(&input)));
+}
+
 #[divan::bench]
 fn bench_part1() {
     part1(divan::black_box(&INPUT));
@@ -12,4 +19,6 @@ fn bench_part1() {
 #[divan::bench]
 fn bench_part2() {
     part2(divan::black_box(&INPUT));
-}
\ No newline at end of file
+}
+
+common::bench_input_sets!({{project-name | pascal_case}});
@@ -1,7 +1,6 @@
-mod {{project-name}};
-use crate::{{project-name}}::{INPUT, part1, part2};
+use {{project-name}}::{{project-name}}::{{project-name | pascal_case}};
+use std::error::Error;
 
-fn main() {
-    println!("Part1: {}", part1(&INPUT));
-    println!("Part2: {}", part2(&INPUT));
-}
\ No newline at end of file
+fn main() -> Result<(), Box<dyn Error>> {
+    common::solution::run(&{{project-name | pascal_case}})
+}
@@ -1,28 +1,72 @@
-use common::read_file_as_string;
+use common::{Answer, Day, ParseError, read_input_or};
 use once_cell::sync::Lazy;
 
-pub static INPUT: Lazy<String> =
-    Lazy::new(|| read_file_as_string("data/{{project-name}}.txt").expect("Failed to load input"));
+/// The example of the puzzle, which stands in for a missing input.
+pub const EXAMPLE: &str = "";
 
-pub fn part1(_input: &str) -> usize {
+pub static INPUT: Lazy<Model> = Lazy::new(|| {
+    {{project-name | pascal_case}}
+        .parse(&read_input_or("{{project-name}}.txt", || EXAMPLE.to_owned()))
+        .expect("Failed to parse input")
+});
+
+pub type Model = Vec<String>;
+
+fn parse_input(input: &str) -> Result<Model, ParseError> {
+    Ok(input.lines().map(str::to_owned).collect())
+}
+
+pub fn part1(_model: &Model) -> usize {
     todo!("{{project-name}} - part1")
 }
 
-pub fn part2(_input: &str) -> usize {
+pub fn part2(_model: &Model) -> usize {
     todo!("{{project-name}} - part1")
 }
 
+pub struct {{project-name | pascal_case}};
+
+impl Day for {{project-name | pascal_case}} {
+    type Model = Model;
+
+    fn day(&self) -> u32 {
+        {{project-name | remove: "day"}}
+    }
+
+    fn title(&self) -> &'static str {
+        todo!("{{project-name}} - title")
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Model, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, model: &Self::Model) -> Answer {
+        Ok(part1(model).to_string())
+    }
+
+    fn part2(&self, model: &Self::Model) -> Option<Answer> {
+        Some(Ok(part2(model).to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::{check_answer, check_input_sets};
 
     #[test]
     fn test_part1() {
-        todo!("{{project-name}} - test - part1")
+        check_answer({{project-name | pascal_case}}.day(), 1, || part1(&INPUT));
     }
 
     #[test]
     fn test_part2() {
-        todo!("{{project-name}} - test - part2")
+        check_answer({{project-name | pascal_case}}.day(), 2, || part2(&INPUT));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_input_sets() {
+        check_input_sets(&{{project-name | pascal_case}});
+    }
+}